@@ -0,0 +1,71 @@
+use super::monoid::{
+    AddMonoid, AdditiveIdentity, Monoid, MulMonoid, MultiplicativeIdentity, XorMonoid,
+};
+use std::ops::{Add, BitXor, Mul, Neg};
+
+/// Monoid in which every element has an inverse `inv`.
+///
+/// Implementations of this trait promise:
+/// `Self::op(Self::inv(a), a) = Self::op(a, Self::inv(a)) = IDENTITY`
+pub trait Group: Monoid {
+    /// The inverse of `x` with respect to the monoid operation.
+    fn inv(x: Self) -> Self;
+}
+
+impl Group for () {
+    fn inv(_: Self) -> Self {}
+}
+
+impl<T: AdditiveIdentity> Group for AddMonoid<T>
+where
+    T: Add<Output = T> + Neg<Output = T>,
+{
+    fn inv(AddMonoid(x): Self) -> Self {
+        AddMonoid(-x)
+    }
+}
+
+impl<T: MultiplicativeIdentity> Group for MulMonoid<T>
+where
+    T: Mul<Output = T> + MultiplicativeInverse,
+{
+    fn inv(MulMonoid(x): Self) -> Self {
+        MulMonoid(x.mul_inv())
+    }
+}
+
+impl<T: AdditiveIdentity> Group for XorMonoid<T>
+where
+    T: BitXor<Output = T>,
+{
+    fn inv(x: Self) -> Self {
+        x
+    }
+}
+
+impl<A: Group> Group for (A,) {
+    fn inv(x: Self) -> Self {
+        (Group::inv(x.0),)
+    }
+}
+
+impl<A: Group, B: Group> Group for (A, B) {
+    fn inv(x: Self) -> Self {
+        (Group::inv(x.0), Group::inv(x.1))
+    }
+}
+
+impl<A: Group, B: Group, C: Group> Group for (A, B, C) {
+    fn inv(x: Self) -> Self {
+        (Group::inv(x.0), Group::inv(x.1), Group::inv(x.2))
+    }
+}
+
+/// Trait for types where (nonzero) elements have a multiplicative inverse.
+///
+/// The law `self.mul_inv() * self = <Self as MultiplicativeIdentity>::VALUE`
+/// should be satisfied for every invertible `self`.
+pub trait MultiplicativeInverse {
+    /// The multiplicative inverse of `self`.
+    fn mul_inv(self) -> Self;
+}
@@ -1,4 +1,6 @@
+pub mod group;
 pub mod monoid;
 mod ring;
-pub use monoid::{Monoid, MonoidAction};
+pub use group::Group;
+pub use monoid::{CommutativeMonoid, Idempotent, Monoid, MonoidAction, Semigroup};
 pub use ring::Ring;
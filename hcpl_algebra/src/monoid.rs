@@ -1,15 +1,19 @@
-/// Set equipped with associative operation `op`
-/// and identity `IDENTITY`.
+/// Set equipped with associative operation `op`.
 ///
 /// Implementations of this trait promise:
 /// `Self::op(Self::op(a, b), c) = Self::op(a, Self::op(b, c))`
+pub trait Semigroup {
+    /// The semigroup operation.
+    fn op(l: Self, r: Self) -> Self;
+}
+
+/// Semigroup with identity `IDENTITY`.
+///
+/// Implementations of this trait promise:
 /// `Self::op(IDENTITY, a) = Self::op(a, IDENTITY) = a`
-pub trait Monoid {
+pub trait Monoid: Semigroup {
     /// The identity element of the monoid.
     const IDENTITY: Self;
-
-    /// The monoid operation.
-    fn op(l: Self, r: Self) -> Self;
 }
 
 /// Monoid that acts on another monoid T.
@@ -24,11 +28,30 @@ pub trait MonoidAction<T: Monoid>: Monoid {
     fn apply(f: &Self, x: &T) -> T;
 }
 
+impl Semigroup for () {
+    fn op(_: Self, _: Self) -> Self {}
+}
+
 impl Monoid for () {
     const IDENTITY: Self = ();
-    fn op(_: Self, _: Self) -> Self {}
 }
 
+/// Monoid whose operation is commutative.
+///
+/// Implementations of this trait promise:
+/// `Self::op(a, b) = Self::op(b, a)`
+pub trait CommutativeMonoid: Monoid {}
+
+impl CommutativeMonoid for () {}
+
+/// Semigroup whose operation is idempotent.
+///
+/// Implementations of this trait promise:
+/// `Self::op(a, a) = a`
+pub trait Idempotent: Semigroup {}
+
+impl Idempotent for () {}
+
 #[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
 pub struct AddMonoid<T>(pub T);
 #[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
@@ -37,15 +60,38 @@ pub struct MulMonoid<T>(pub T);
 pub struct MinMonoid<T>(pub T);
 #[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
 pub struct MaxMonoid<T>(pub T);
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+pub struct XorMonoid<T>(pub T);
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+pub struct AndMonoid<T>(pub T);
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+pub struct OrMonoid<T>(pub T);
+/// Monoid of non-negative integers under the greatest common divisor.
+#[derive(Clone, Copy, Eq, PartialEq, Ord, PartialOrd, Debug, Hash)]
+pub struct GcdMonoid<T>(pub T);
+
+impl<T> Semigroup for AddMonoid<T>
+where
+    T: std::ops::Add<Output = T>,
+{
+    fn op(AddMonoid(l): Self, AddMonoid(r): Self) -> Self {
+        AddMonoid(l + r)
+    }
+}
 
 impl<T: AdditiveIdentity> Monoid for AddMonoid<T>
 where
     T: std::ops::Add<Output = T>,
 {
     const IDENTITY: Self = AddMonoid(<T as AdditiveIdentity>::VALUE);
+}
 
-    fn op(AddMonoid(l): Self, AddMonoid(r): Self) -> Self {
-        AddMonoid(l + r)
+impl<T> Semigroup for MulMonoid<T>
+where
+    T: std::ops::Mul<Output = T>,
+{
+    fn op(MulMonoid(l): Self, MulMonoid(r): Self) -> Self {
+        MulMonoid(l * r)
     }
 }
 
@@ -54,9 +100,15 @@ where
     T: std::ops::Mul<Output = T>,
 {
     const IDENTITY: Self = MulMonoid(<T as MultiplicativeIdentity>::VALUE);
+}
 
-    fn op(MulMonoid(l): Self, MulMonoid(r): Self) -> Self {
-        MulMonoid(l * r)
+impl<T> Semigroup for MinMonoid<T>
+where
+    T: Clone,
+    T: std::cmp::Ord,
+{
+    fn op(MinMonoid(l): Self, MinMonoid(r): Self) -> Self {
+        MinMonoid(std::cmp::min(l, r))
     }
 }
 
@@ -66,9 +118,15 @@ where
     T: std::cmp::Ord,
 {
     const IDENTITY: Self = MinMonoid(<T as MinimumIdentity>::VALUE);
+}
 
-    fn op(MinMonoid(l): Self, MinMonoid(r): Self) -> Self {
-        MinMonoid(std::cmp::min(l, r))
+impl<T> Semigroup for MaxMonoid<T>
+where
+    T: Clone,
+    T: std::cmp::Ord,
+{
+    fn op(MaxMonoid(l): Self, MaxMonoid(r): Self) -> Self {
+        MaxMonoid(std::cmp::max(l, r))
     }
 }
 
@@ -78,43 +136,137 @@ where
     T: std::cmp::Ord,
 {
     const IDENTITY: Self = MaxMonoid(<T as MaximumIdentity>::VALUE);
+}
 
-    fn op(MaxMonoid(l): Self, MaxMonoid(r): Self) -> Self {
-        MaxMonoid(std::cmp::max(l, r))
+impl<T> Semigroup for XorMonoid<T>
+where
+    T: std::ops::BitXor<Output = T>,
+{
+    fn op(XorMonoid(l): Self, XorMonoid(r): Self) -> Self {
+        XorMonoid(l ^ r)
+    }
+}
+
+impl<T: AdditiveIdentity> Monoid for XorMonoid<T>
+where
+    T: std::ops::BitXor<Output = T>,
+{
+    const IDENTITY: Self = XorMonoid(<T as AdditiveIdentity>::VALUE);
+}
+
+impl<T> Semigroup for AndMonoid<T>
+where
+    T: std::ops::BitAnd<Output = T>,
+{
+    fn op(AndMonoid(l): Self, AndMonoid(r): Self) -> Self {
+        AndMonoid(l & r)
+    }
+}
+
+impl<T: BitAndIdentity> Monoid for AndMonoid<T>
+where
+    T: std::ops::BitAnd<Output = T>,
+{
+    const IDENTITY: Self = AndMonoid(<T as BitAndIdentity>::VALUE);
+}
+
+impl<T> Semigroup for OrMonoid<T>
+where
+    T: std::ops::BitOr<Output = T>,
+{
+    fn op(OrMonoid(l): Self, OrMonoid(r): Self) -> Self {
+        OrMonoid(l | r)
+    }
+}
+
+impl<T: AdditiveIdentity> Monoid for OrMonoid<T>
+where
+    T: std::ops::BitOr<Output = T>,
+{
+    const IDENTITY: Self = OrMonoid(<T as AdditiveIdentity>::VALUE);
+}
+
+impl<T: AdditiveIdentity> Semigroup for GcdMonoid<T>
+where
+    T: Copy + Eq + std::ops::Rem<Output = T>,
+{
+    fn op(GcdMonoid(mut l): Self, GcdMonoid(mut r): Self) -> Self {
+        while r != T::VALUE {
+            (l, r) = (r, l % r);
+        }
+        GcdMonoid(l)
+    }
+}
+
+impl<T: AdditiveIdentity> Monoid for GcdMonoid<T>
+where
+    T: Copy + Eq + std::ops::Rem<Output = T>,
+{
+    const IDENTITY: Self = GcdMonoid(<T as AdditiveIdentity>::VALUE);
+}
+
+impl<T: AdditiveIdentity> CommutativeMonoid for AddMonoid<T> where T: std::ops::Add<Output = T> {}
+impl<T: MultiplicativeIdentity> CommutativeMonoid for MulMonoid<T> where T: std::ops::Mul<Output = T>
+{}
+impl<T: MinimumIdentity> CommutativeMonoid for MinMonoid<T> where T: Clone + std::cmp::Ord {}
+impl<T: MaximumIdentity> CommutativeMonoid for MaxMonoid<T> where T: Clone + std::cmp::Ord {}
+impl<T: AdditiveIdentity> CommutativeMonoid for XorMonoid<T> where T: std::ops::BitXor<Output = T> {}
+impl<T: BitAndIdentity> CommutativeMonoid for AndMonoid<T> where T: std::ops::BitAnd<Output = T> {}
+impl<T: AdditiveIdentity> CommutativeMonoid for OrMonoid<T> where T: std::ops::BitOr<Output = T> {}
+impl<T: AdditiveIdentity> CommutativeMonoid for GcdMonoid<T> where
+    T: Copy + Eq + std::ops::Rem<Output = T>
+{
+}
+
+impl<T> Idempotent for MinMonoid<T> where T: Clone + std::cmp::Ord {}
+impl<T> Idempotent for MaxMonoid<T> where T: Clone + std::cmp::Ord {}
+impl<T> Idempotent for AndMonoid<T> where T: std::ops::BitAnd<Output = T> {}
+impl<T> Idempotent for OrMonoid<T> where T: std::ops::BitOr<Output = T> {}
+impl<T: AdditiveIdentity> Idempotent for GcdMonoid<T> where T: Copy + Eq + std::ops::Rem<Output = T> {}
+
+impl<A: Semigroup> Semigroup for (A,) {
+    fn op(l: Self, r: Self) -> Self {
+        (Semigroup::op(l.0, r.0),)
     }
 }
 
 impl<A: Monoid> Monoid for (A,) {
     const IDENTITY: Self = (A::IDENTITY,);
+}
 
+impl<A: Semigroup, B: Semigroup> Semigroup for (A, B) {
     fn op(l: Self, r: Self) -> Self {
-        (
-            Monoid::op(l.0, r.0),
-        )
+        (Semigroup::op(l.0, r.0), Semigroup::op(l.1, r.1))
     }
 }
 
 impl<A: Monoid, B: Monoid> Monoid for (A, B) {
     const IDENTITY: Self = (A::IDENTITY, B::IDENTITY);
+}
 
+impl<A: Semigroup, B: Semigroup, C: Semigroup> Semigroup for (A, B, C) {
     fn op(l: Self, r: Self) -> Self {
         (
-            Monoid::op(l.0, r.0),
-            Monoid::op(l.1, r.1),
+            Semigroup::op(l.0, r.0),
+            Semigroup::op(l.1, r.1),
+            Semigroup::op(l.2, r.2),
         )
     }
 }
 
 impl<A: Monoid, B: Monoid, C: Monoid> Monoid for (A, B, C) {
     const IDENTITY: Self = (A::IDENTITY, B::IDENTITY, C::IDENTITY);
+}
 
-    fn op(l: Self, r: Self) -> Self {
-        (
-            Monoid::op(l.0, r.0),
-            Monoid::op(l.1, r.1),
-            Monoid::op(l.2, r.2),
-        )
-    }
+impl<A: Idempotent> Idempotent for (A,) {}
+impl<A: Idempotent, B: Idempotent> Idempotent for (A, B) {}
+impl<A: Idempotent, B: Idempotent, C: Idempotent> Idempotent for (A, B, C) {}
+
+impl<A: CommutativeMonoid> CommutativeMonoid for (A,) {}
+impl<A: CommutativeMonoid, B: CommutativeMonoid> CommutativeMonoid for (A, B) {}
+impl<A: CommutativeMonoid, B: CommutativeMonoid, C: CommutativeMonoid> CommutativeMonoid
+    for (A, B, C)
+{
 }
 
 /// Trait for types with an additive identity.
@@ -149,6 +301,14 @@ pub trait MaximumIdentity {
     const VALUE: Self;
 }
 
+/// Trait for types with an identity with respect to bitwise and.
+///
+/// The law `<Self as BitAndIdentity>::VALUE & n = n` should be satisfied.
+pub trait BitAndIdentity {
+    /// The bitwise and identity of `Self`, with all bits set.
+    const VALUE: Self;
+}
+
 macro_rules! impl_identities_for {
     ($t:ty) => {
         impl AdditiveIdentity for $t {
@@ -163,6 +323,9 @@ macro_rules! impl_identities_for {
         impl MaximumIdentity for $t {
             const VALUE: Self = Self::MIN;
         }
+        impl BitAndIdentity for $t {
+            const VALUE: Self = !0;
+        }
     };
 }
 
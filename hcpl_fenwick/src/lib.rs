@@ -0,0 +1,154 @@
+use hcpl_algebra::{CommutativeMonoid, Group};
+use std::{iter::FromIterator, ops::RangeBounds};
+
+pub use hcpl_algebra::monoid;
+
+#[derive(Clone, Debug)]
+/// A Fenwick tree (binary indexed tree) with values of type `T`.
+///
+/// ```
+/// use hcpl_fenwick::monoid::AddMonoid;
+/// use hcpl_fenwick::FenwickTree;
+///
+/// let mut tree: FenwickTree<_> = [3, 1, 4, 1, 5].map(AddMonoid).into_iter().collect();
+/// tree.add(2, &AddMonoid(2)); // [3, 1, 6, 1, 5]
+/// assert_eq!(tree.prefix(3), AddMonoid(10));
+/// assert_eq!(tree.fold(1..4), AddMonoid(8));
+/// assert_eq!(tree.get(2), AddMonoid(6));
+/// // the first index at which the prefix sum reaches 11
+/// assert_eq!(tree.prefix_while(|s| s.0 < 11), (3, AddMonoid(10)));
+/// ```
+pub struct FenwickTree<T: CommutativeMonoid + Clone> {
+    values: Vec<T>,
+}
+
+impl<T: CommutativeMonoid + Clone> FenwickTree<T> {
+    /// Creates a Fenwick tree from a `Vec` in O(n).
+    fn from_vec(mut values: Vec<T>) -> Self {
+        let n = values.len();
+        for i in 1..=n {
+            let j = i + (i & i.wrapping_neg());
+            if j <= n {
+                values[j - 1] = T::op(values[j - 1].clone(), values[i - 1].clone());
+            }
+        }
+        Self { values }
+    }
+
+    /// Creates a Fenwick tree from a slice.
+    pub fn new(src: &[T]) -> Self {
+        Self::from_vec(src.to_vec())
+    }
+
+    /// Creates a Fenwick tree initialised with `T::IDENTITY`.
+    pub fn with_size(size: usize) -> Self {
+        Self {
+            values: vec![T::IDENTITY; size],
+        }
+    }
+
+    /// Returns the number of elements in the tree.
+    pub fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    /// Returns `true` if the tree contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    /// Replaces `tree[index]` with `T::op(tree[index], value)`.
+    pub fn add(&mut self, index: usize, value: &T) {
+        debug_assert!(index < self.len());
+
+        let mut i = index + 1;
+        while i <= self.len() {
+            self.values[i - 1] = T::op(self.values[i - 1].clone(), value.clone());
+            i += i & i.wrapping_neg();
+        }
+    }
+
+    /// Returns the monoid fold of the values in `0..end`.
+    pub fn prefix(&self, end: usize) -> T {
+        debug_assert!(end <= self.len());
+
+        let mut i = end;
+        let mut res = T::IDENTITY;
+        while i != 0 {
+            res = T::op(self.values[i - 1].clone(), res);
+            i &= i - 1;
+        }
+        res
+    }
+
+    /// Returns the largest `end` such that `predicate` holds for the fold of
+    /// `0..end`, along with that fold, assuming `predicate` is monotone.
+    ///
+    /// With `T = AddMonoid<_>` over non-negative values and `predicate = |s| s.0 < x`,
+    /// this is the first index at which the prefix sum reaches `x`.
+    ///
+    /// `predicate(&T::IDENTITY)` must hold.
+    pub fn prefix_while<P: Fn(&T) -> bool>(&self, predicate: P) -> (usize, T) {
+        debug_assert!(predicate(&T::IDENTITY));
+
+        let n = self.len();
+        let mut pos = 0;
+        let mut sum = T::IDENTITY;
+
+        if n == 0 {
+            return (pos, sum);
+        }
+
+        let mut step = 1 << (usize::BITS - 1 - n.leading_zeros());
+        while step != 0 {
+            if pos + step <= n {
+                let nxt_sum = T::op(sum.clone(), self.values[pos + step - 1].clone());
+                if predicate(&nxt_sum) {
+                    pos += step;
+                    sum = nxt_sum;
+                }
+            }
+            step >>= 1;
+        }
+
+        (pos, sum)
+    }
+}
+
+impl<T: CommutativeMonoid + Group + Clone> FenwickTree<T> {
+    /// Returns the monoid fold of all the values in the given `range`.
+    pub fn fold<R>(&self, range: R) -> T
+    where
+        R: RangeBounds<usize>,
+    {
+        use std::ops::Bound::*;
+
+        let start = match range.start_bound() {
+            Included(&n) => n,
+            Excluded(&n) => n + 1,
+            Unbounded => 0,
+        };
+        let end = match range.end_bound() {
+            Included(&n) => n + 1,
+            Excluded(&n) => n,
+            Unbounded => self.len(),
+        };
+
+        if start >= end {
+            return T::IDENTITY;
+        }
+
+        T::op(self.prefix(end), T::inv(self.prefix(start)))
+    }
+
+    /// Returns the value at the given `index`.
+    pub fn get(&self, index: usize) -> T {
+        self.fold(index..=index)
+    }
+}
+
+impl<T: CommutativeMonoid + Clone> FromIterator<T> for FenwickTree<T> {
+    fn from_iter<It: IntoIterator<Item = T>>(iter: It) -> Self {
+        Self::from_vec(iter.into_iter().collect())
+    }
+}
@@ -0,0 +1,421 @@
+use hcpl_modnum::Modnum;
+use hcpl_ntt::convolution;
+use std::ops::{Add, AddAssign, Div, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+/// A formal power series (or polynomial) over ℤ/mℤ where m = MOD.
+///
+/// `self.0[i]` is the coefficient of x^i. Multiplication uses
+/// [`hcpl_ntt::convolution`], so `MOD` must be an NTT-friendly prime.
+pub struct Fps<const MOD: u32>(pub Vec<Modnum<MOD>>);
+
+/// Calculates a square root of `a` using Tonelli–Shanks, **assuming `MOD` is an odd prime**.
+fn sqrt_mod<const MOD: u32>(a: Modnum<MOD>) -> Option<Modnum<MOD>> {
+    let zero = Modnum::new(0);
+    let one = Modnum::new(1);
+
+    if a == zero {
+        return Some(zero);
+    }
+    if a.pow((MOD as usize - 1) / 2) != one {
+        return None;
+    }
+
+    let s = (MOD - 1).trailing_zeros();
+    let q = (MOD as usize - 1) >> s;
+
+    let mut z = Modnum::new(2);
+    while z.pow((MOD as usize - 1) / 2) == one {
+        z += one;
+    }
+
+    let mut m = s;
+    let mut c = z.pow(q);
+    let mut t = a.pow(q);
+    let mut r = a.pow(q.div_ceil(2));
+
+    while t != one {
+        let mut i = 0;
+        let mut t2 = t;
+        while t2 != one {
+            t2 *= t2;
+            i += 1;
+        }
+        let b = c.pow(1 << (m - i - 1));
+        m = i;
+        c = b * b;
+        t *= c;
+        r *= b;
+    }
+
+    Some(r)
+}
+
+impl<const MOD: u32> Fps<MOD> {
+    const ZERO: Modnum<MOD> = Modnum::new(0);
+    const ONE: Modnum<MOD> = Modnum::new(1);
+
+    /// Creates a formal power series from its coefficients.
+    pub fn new(coefficients: Vec<Modnum<MOD>>) -> Self {
+        Self(coefficients)
+    }
+
+    /// Returns the number of stored coefficients.
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    /// Returns `true` if no coefficients are stored.
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Returns the coefficient of x^i.
+    pub fn coeff(&self, i: usize) -> Modnum<MOD> {
+        self.0.get(i).copied().unwrap_or(Self::ZERO)
+    }
+
+    /// Returns `self mod x^n`, padded with zeros to exactly `n` coefficients.
+    pub fn prefix(&self, n: usize) -> Self {
+        let mut res = self.0[..n.min(self.len())].to_vec();
+        res.resize(n, Self::ZERO);
+        Self(res)
+    }
+
+    /// Removes trailing zero coefficients.
+    pub fn trim(&mut self) {
+        while self.0.last() == Some(&Self::ZERO) {
+            self.0.pop();
+        }
+    }
+
+    /// Returns the formal derivative of `self`.
+    pub fn derivative(&self) -> Self {
+        Self(
+            self.0
+                .iter()
+                .enumerate()
+                .skip(1)
+                .map(|(i, &a)| a * Modnum::from(i))
+                .collect(),
+        )
+    }
+
+    /// Returns the formal integral of `self` with constant term zero.
+    pub fn integral(&self) -> Self {
+        let n = self.len();
+        let mut invs = vec![Self::ONE; n + 1];
+        for i in 2..=n {
+            invs[i] = -invs[MOD as usize % i] * Modnum::from(MOD as usize / i);
+        }
+
+        let mut res = Vec::with_capacity(n + 1);
+        res.push(Self::ZERO);
+        res.extend(self.0.iter().zip(&invs[1..]).map(|(&a, &inv)| a * inv));
+        Self(res)
+    }
+
+    /// Returns the first `n` coefficients of `1 / self`.
+    ///
+    /// The constant term of `self` must be invertible.
+    ///
+    /// ```
+    /// use hcpl_fps::Fps;
+    /// use hcpl_modnum::Modnum;
+    ///
+    /// let fps = |v: &[u32]| Fps::<998244353>::new(v.iter().map(|&x| Modnum::new(x)).collect());
+    /// let f = fps(&[1, 998244352]); // 1 - x
+    /// assert_eq!(f.inv(4), fps(&[1, 1, 1, 1]));
+    /// ```
+    pub fn inv(&self, n: usize) -> Self {
+        assert!(self.coeff(0) != Self::ZERO);
+
+        let mut g = Self(vec![self.0[0].inv()]);
+        let mut len = 1;
+        while len < n {
+            len *= 2;
+            // g <- g (2 - f g)
+            let mut t = -(&self.prefix(len) * &g).prefix(len);
+            t.0[0] += Modnum::new(2);
+            g = (&g * &t).prefix(len);
+        }
+        g.prefix(n)
+    }
+
+    /// Returns the first `n` coefficients of `log(self)`.
+    ///
+    /// The constant term of `self` must be 1.
+    ///
+    /// ```
+    /// use hcpl_fps::Fps;
+    /// use hcpl_modnum::Modnum;
+    ///
+    /// let fps = |v: &[u32]| Fps::<998244353>::new(v.iter().map(|&x| Modnum::new(x)).collect());
+    /// let f = fps(&[1, 3, 1, 4, 1, 5]);
+    /// assert_eq!(f.log(6).exp(6), f);
+    /// ```
+    pub fn log(&self, n: usize) -> Self {
+        assert!(self.coeff(0) == Self::ONE);
+
+        if n == 0 {
+            return Self(Vec::new());
+        }
+        (&self.derivative() * &self.inv(n)).prefix(n - 1).integral()
+    }
+
+    /// Returns the first `n` coefficients of `exp(self)`.
+    ///
+    /// The constant term of `self` must be 0.
+    ///
+    /// ```
+    /// use hcpl_fps::Fps;
+    /// use hcpl_modnum::Modnum;
+    ///
+    /// let fps = |v: &[u32]| Fps::<998244353>::new(v.iter().map(|&x| Modnum::new(x)).collect());
+    /// let f = fps(&[0, 2, 7, 1, 8]);
+    /// assert_eq!(f.exp(5).log(5), f);
+    /// ```
+    pub fn exp(&self, n: usize) -> Self {
+        assert!(self.coeff(0) == Self::ZERO);
+
+        let mut g = Self(vec![Self::ONE]);
+        let mut len = 1;
+        while len < n {
+            len *= 2;
+            // g <- g (1 - log(g) + f)
+            let mut t = self.prefix(len) - g.log(len);
+            t.0[0] += Self::ONE;
+            g = (&g * &t).prefix(len);
+        }
+        g.prefix(n)
+    }
+
+    /// Returns the first `n` coefficients of a square root of `self`,
+    /// or `None` if no square root exists. `MOD` must be odd.
+    ///
+    /// ```
+    /// use hcpl_fps::Fps;
+    /// use hcpl_modnum::Modnum;
+    ///
+    /// let fps = |v: &[u32]| Fps::<998244353>::new(v.iter().map(|&x| Modnum::new(x)).collect());
+    /// let f = fps(&[4, 12, 9]); // (2 + 3x)^2
+    /// let g = f.sqrt(3).unwrap();
+    /// assert_eq!((&g * &g).prefix(3), f);
+    /// assert_eq!(fps(&[0, 1]).sqrt(2), None);
+    /// ```
+    pub fn sqrt(&self, n: usize) -> Option<Self> {
+        let d = match self.0.iter().position(|&a| a != Self::ZERO) {
+            Some(d) => d,
+            None => return Some(Self(vec![Self::ZERO; n])),
+        };
+        if d % 2 == 1 {
+            return None;
+        }
+        if d / 2 >= n {
+            return Some(Self(vec![Self::ZERO; n]));
+        }
+
+        let m = n - d / 2;
+        let f = Self(self.0[d..].to_vec());
+        let inv2 = Modnum::new(2).inv();
+
+        let mut g = Self(vec![sqrt_mod(f.0[0])?]);
+        let mut len = 1;
+        while len < m {
+            len *= 2;
+            // g <- (g + f / g) / 2
+            g = (&g + &(&f.prefix(len) * &g.inv(len)).prefix(len)) * inv2;
+        }
+
+        let mut res = vec![Self::ZERO; d / 2];
+        res.extend(g.prefix(m).0);
+        Some(Self(res))
+    }
+
+    /// Returns the first `n` coefficients of `self^k`.
+    ///
+    /// ```
+    /// use hcpl_fps::Fps;
+    /// use hcpl_modnum::Modnum;
+    ///
+    /// let fps = |v: &[u32]| Fps::<998244353>::new(v.iter().map(|&x| Modnum::new(x)).collect());
+    /// let f = fps(&[0, 1, 1]); // x + x^2
+    /// assert_eq!(f.pow(2, 5), fps(&[0, 0, 1, 2, 1]));
+    /// ```
+    pub fn pow(&self, k: usize, n: usize) -> Self {
+        if k == 0 {
+            return Self(vec![Self::ONE]).prefix(n);
+        }
+
+        let d = match self.0.iter().position(|&a| a != Self::ZERO) {
+            Some(d) => d,
+            None => return Self(vec![Self::ZERO; n]),
+        };
+        let shift = match d.checked_mul(k) {
+            Some(shift) if shift < n => shift,
+            _ => return Self(vec![Self::ZERO; n]),
+        };
+
+        let m = n - shift;
+        let c = self.0[d];
+        let f = Self(self.0[d..].to_vec()).prefix(m) * c.inv();
+        let g = (f.log(m) * Modnum::from(k)).exp(m) * c.pow(k);
+
+        let mut res = vec![Self::ZERO; shift];
+        res.extend(g.0);
+        Self(res)
+    }
+
+    /// Polynomial division with remainder, returning `(q, r)` such that
+    /// `self = q * rhs + r` and `deg(r) < deg(rhs)`.
+    ///
+    /// Both `q` and `r` are trimmed.
+    ///
+    /// ```
+    /// use hcpl_fps::Fps;
+    /// use hcpl_modnum::Modnum;
+    ///
+    /// let fps = |v: &[u32]| Fps::<998244353>::new(v.iter().map(|&x| Modnum::new(x)).collect());
+    /// let f = fps(&[5, 3, 3, 1]); // (x + 1)^3 + 4
+    /// let g = fps(&[1, 1]); // x + 1
+    /// assert_eq!(f.div_rem(&g), (fps(&[1, 2, 1]), fps(&[4])));
+    /// ```
+    pub fn div_rem(&self, rhs: &Self) -> (Self, Self) {
+        let mut a = self.clone();
+        a.trim();
+        let mut b = rhs.clone();
+        b.trim();
+        assert!(!b.is_empty());
+
+        if a.len() < b.len() {
+            return (Self(Vec::new()), a);
+        }
+
+        let q_len = a.len() - b.len() + 1;
+        let mut ra = a.clone();
+        ra.0.reverse();
+        let mut rb = b.clone();
+        rb.0.reverse();
+
+        let mut q = (&ra.prefix(q_len) * &rb.inv(q_len)).prefix(q_len);
+        q.0.reverse();
+
+        let mut r = a - &b * &q;
+        r.0.truncate(b.len() - 1);
+        r.trim();
+        q.trim();
+        (q, r)
+    }
+}
+
+impl<const MOD: u32> Add<&Fps<MOD>> for &Fps<MOD> {
+    type Output = Fps<MOD>;
+
+    fn add(self, rhs: &Fps<MOD>) -> Self::Output {
+        let mut res = self.clone();
+        res += rhs;
+        res
+    }
+}
+
+impl<const MOD: u32> Sub<&Fps<MOD>> for &Fps<MOD> {
+    type Output = Fps<MOD>;
+
+    fn sub(self, rhs: &Fps<MOD>) -> Self::Output {
+        let mut res = self.clone();
+        res -= rhs;
+        res
+    }
+}
+
+impl<const MOD: u32> Mul<&Fps<MOD>> for &Fps<MOD> {
+    type Output = Fps<MOD>;
+
+    fn mul(self, rhs: &Fps<MOD>) -> Self::Output {
+        Fps(convolution(&self.0, &rhs.0))
+    }
+}
+
+impl<const MOD: u32> Div<&Fps<MOD>> for &Fps<MOD> {
+    type Output = Fps<MOD>;
+
+    fn div(self, rhs: &Fps<MOD>) -> Self::Output {
+        self.div_rem(rhs).0
+    }
+}
+
+impl<const MOD: u32> Rem<&Fps<MOD>> for &Fps<MOD> {
+    type Output = Fps<MOD>;
+
+    fn rem(self, rhs: &Fps<MOD>) -> Self::Output {
+        self.div_rem(rhs).1
+    }
+}
+
+macro_rules! forward_owned_binop {
+    ($tr:ident, $f:ident) => {
+        impl<const MOD: u32> $tr for Fps<MOD> {
+            type Output = Fps<MOD>;
+
+            fn $f(self, rhs: Fps<MOD>) -> Self::Output {
+                (&self).$f(&rhs)
+            }
+        }
+    };
+}
+
+forward_owned_binop!(Add, add);
+forward_owned_binop!(Sub, sub);
+forward_owned_binop!(Mul, mul);
+forward_owned_binop!(Div, div);
+forward_owned_binop!(Rem, rem);
+
+impl<const MOD: u32> Neg for Fps<MOD> {
+    type Output = Self;
+
+    fn neg(mut self) -> Self::Output {
+        for a in self.0.iter_mut() {
+            *a = -*a;
+        }
+        self
+    }
+}
+
+impl<const MOD: u32> AddAssign<&Fps<MOD>> for Fps<MOD> {
+    fn add_assign(&mut self, rhs: &Fps<MOD>) {
+        if self.len() < rhs.len() {
+            self.0.resize(rhs.len(), Modnum::new(0));
+        }
+        for (a, &b) in self.0.iter_mut().zip(&rhs.0) {
+            *a += b;
+        }
+    }
+}
+
+impl<const MOD: u32> SubAssign<&Fps<MOD>> for Fps<MOD> {
+    fn sub_assign(&mut self, rhs: &Fps<MOD>) {
+        if self.len() < rhs.len() {
+            self.0.resize(rhs.len(), Modnum::new(0));
+        }
+        for (a, &b) in self.0.iter_mut().zip(&rhs.0) {
+            *a -= b;
+        }
+    }
+}
+
+impl<const MOD: u32> MulAssign<&Fps<MOD>> for Fps<MOD> {
+    fn mul_assign(&mut self, rhs: &Fps<MOD>) {
+        *self = &*self * rhs;
+    }
+}
+
+impl<const MOD: u32> Mul<Modnum<MOD>> for Fps<MOD> {
+    type Output = Self;
+
+    fn mul(mut self, rhs: Modnum<MOD>) -> Self::Output {
+        for a in self.0.iter_mut() {
+            *a *= rhs;
+        }
+        self
+    }
+}
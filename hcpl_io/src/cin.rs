@@ -1,32 +1,152 @@
-use std::io::BufRead;
+use std::io::{BufRead, Read};
 
 use hcpl_integer::Integer;
 
-pub struct Cin<'a> {
-    stdin: std::io::StdinLock<'a>,
+/// A fast token reader over any [`BufRead`] source, reading from stdin by default.
+///
+/// ```
+/// use hcpl_io::Cin;
+///
+/// let mut cin = Cin::from_reader("3\n-1 2 x\n".as_bytes());
+/// assert_eq!(cin.get::<usize>(), 3);
+/// assert_eq!(cin.get::<i32>(), -1);
+/// assert_eq!(cin.get::<u8>(), 2);
+/// assert_eq!(cin.get::<char>(), 'x');
+/// ```
+///
+/// With `STRICT = true`, as returned by [`Cin::strict`], integer reads are validated.
+/// It is a type parameter so that the default mode pays nothing for it.
+pub struct Cin<R: BufRead = std::io::StdinLock<'static>, const STRICT: bool = false> {
+    reader: R,
+    /// Whether no token was read since the last [`Cin::read_line`], or since the start.
+    at_line_start: bool,
+    /// The number of bytes consumed so far, only tracked when `STRICT`.
+    offset: usize,
 }
 
-impl<'a> Cin<'a> {
-    pub fn new(stdin: &'a std::io::Stdin) -> Self {
+impl Cin {
+    pub fn new(stdin: &std::io::Stdin) -> Self {
+        Self::from_reader(stdin.lock())
+    }
+}
+
+impl Cin<SliceReader> {
+    /// Reads all of stdin into memory up front, which is faster than
+    /// [`Cin::new`] for huge inputs but unsuitable for interactive problems.
+    pub fn slurp(stdin: &std::io::Stdin) -> Self {
+        let mut buffer = Vec::new();
+        std::io::Read::read_to_end(&mut stdin.lock(), &mut buffer).unwrap();
+        Self::from_reader(SliceReader::new(buffer.into_boxed_slice()))
+    }
+}
+
+/// A whole input held in memory, as read by [`Cin::slurp`].
+///
+/// `fill_buf` always returns all of the remaining input, so every token is
+/// scanned directly in one slice, and reading moves an index instead of
+/// refilling a buffer.
+///
+/// ```
+/// use hcpl_io::{Cin, SliceReader};
+///
+/// let mut cin = Cin::from_reader(SliceReader::new(b"2\n-7 x".to_vec().into_boxed_slice()));
+/// assert_eq!(cin.get::<usize>(), 2);
+/// assert_eq!(cin.get::<i64>(), -7);
+/// assert_eq!(cin.get::<char>(), 'x');
+/// assert!(cin.is_eof());
+/// ```
+pub struct SliceReader {
+    data: Box<[u8]>,
+    pos: usize,
+}
+
+impl SliceReader {
+    /// Creates a reader over `data`.
+    pub fn new(data: Box<[u8]>) -> Self {
+        Self { data, pos: 0 }
+    }
+}
+
+impl Read for SliceReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let rest = &self.data[self.pos..];
+        let n = rest.len().min(buf.len());
+        buf[..n].copy_from_slice(&rest[..n]);
+        self.pos += n;
+        Ok(n)
+    }
+}
+
+impl BufRead for SliceReader {
+    #[inline]
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        Ok(&self.data[self.pos..])
+    }
+
+    #[inline]
+    fn consume(&mut self, amt: usize) {
+        debug_assert!(self.pos + amt <= self.data.len());
+        self.pos += amt;
+    }
+}
+
+impl<R: BufRead> Cin<R> {
+    /// Creates a `Cin` reading from `reader`, eg. a `&[u8]` or a `BufReader<File>`.
+    pub fn from_reader(reader: R) -> Self {
         Self {
-            stdin: stdin.lock(),
+            reader,
+            at_line_start: true,
+            offset: 0,
         }
     }
 
+    /// Makes integer reads validate their input, for writing input validators.
+    ///
+    /// Instead of silently returning a wrong number, reading a malformed or
+    /// out of range integer panics with the token and its byte offset.
+    ///
+    /// ```should_panic
+    /// use hcpl_io::Cin;
+    ///
+    /// let mut cin = Cin::from_reader("12 300".as_bytes()).strict();
+    /// assert_eq!(cin.get::<u8>(), 12);
+    /// cin.get::<u8>(); // panics with "integer out of range for u8: "300" at byte 3"
+    /// ```
+    pub fn strict(self) -> Cin<R, true> {
+        Cin {
+            reader: self.reader,
+            at_line_start: self.at_line_start,
+            offset: 0,
+        }
+    }
+}
+
+impl<R: BufRead> Cin<R, true> {
+    /// Returns the number of bytes consumed since [`Cin::strict`] was called.
+    pub fn offset(&self) -> usize {
+        self.offset
+    }
+}
+
+impl<R: BufRead, const STRICT: bool> Cin<R, STRICT> {
     pub fn buffer(&mut self) -> &[u8] {
-        self.stdin.fill_buf().unwrap()
+        self.reader.fill_buf().unwrap()
     }
 
     pub fn consume(&mut self, amt: usize) {
-        self.stdin.consume(amt)
+        if STRICT {
+            self.offset += amt;
+        }
+        self.reader.consume(amt)
     }
 
+    /// Reads bytes until `predicate` holds or the input ends.
     pub fn read_until<P: FnMut(u8) -> bool>(&mut self, mut predicate: P) -> Vec<u8> {
         let mut b = self.buffer();
 
         let mut res = Vec::new();
 
-        loop {
+        while !b.is_empty() {
             match b.iter().copied().position(&mut predicate) {
                 Some(i) => {
                     res.extend_from_slice(&b[..i]);
@@ -45,9 +165,10 @@ impl<'a> Cin<'a> {
         res
     }
 
+    /// Discards bytes until `predicate` holds or the input ends.
     pub fn discard_until<P: FnMut(u8) -> bool>(&mut self, mut predicate: P) {
         let mut b = self.buffer();
-        loop {
+        while !b.is_empty() {
             match b.iter().copied().position(&mut predicate) {
                 Some(i) => {
                     self.consume(i);
@@ -62,22 +183,160 @@ impl<'a> Cin<'a> {
         }
     }
 
+    /// Discards whitespace up to the next token.
     pub fn discard_whitespace(&mut self) {
+        self.at_line_start = false;
         self.discard_until(|b| !b.is_ascii_whitespace());
     }
 
+    /// Reads a line, without its line break.
+    ///
+    /// If a token was read last, the rest of its line is skipped first when it
+    /// holds only whitespace, so that a line can be read right after eg. its length.
+    /// Returns an empty line at the end of the input.
+    ///
+    /// ```
+    /// use hcpl_io::Cin;
+    ///
+    /// let mut cin = Cin::from_reader("2 \t\r\n a b \r\n\nc".as_bytes());
+    /// assert_eq!(cin.get::<u8>(), 2);
+    /// assert_eq!(cin.read_line(), b" a b ");
+    /// assert_eq!(cin.read_line(), b"");
+    /// assert_eq!(cin.read_line(), b"c");
+    /// ```
+    pub fn read_line(&mut self) -> Vec<u8> {
+        self.try_read_line().unwrap_or_default()
+    }
+
+    /// Reads a line like [`Cin::read_line`], or returns `None` at the end of the input.
+    ///
+    /// ```
+    /// use hcpl_io::Cin;
+    ///
+    /// let mut cin = Cin::from_reader("1  \nx y\n\n".as_bytes());
+    /// assert_eq!(cin.get::<u8>(), 1);
+    /// assert_eq!(cin.try_read_line(), Some(b"x y".to_vec()));
+    /// assert_eq!(cin.try_read_line(), Some(b"".to_vec()));
+    /// assert_eq!(cin.try_read_line(), None);
+    /// ```
+    pub fn try_read_line(&mut self) -> Option<Vec<u8>> {
+        let mut res = Vec::new();
+        if !self.at_line_start {
+            res = self.read_until(|b| !matches!(b, b' ' | b'\t' | b'\r'));
+            match self.buffer().first() {
+                Some(b'\n') => {
+                    res.clear();
+                    self.consume(1);
+                }
+                Some(_) => {}
+                None => return None,
+            }
+        }
+
+        if res.is_empty() && self.buffer().is_empty() {
+            return None;
+        }
+        res.extend(self.read_until(|b| b == b'\n'));
+        if !self.buffer().is_empty() {
+            self.consume(1);
+        }
+        if res.last() == Some(&b'\r') {
+            res.pop();
+        }
+        self.at_line_start = true;
+        Some(res)
+    }
+
+    /// Returns `true` if only whitespace is left in the input.
+    pub fn is_eof(&mut self) -> bool {
+        self.discard_whitespace();
+        self.buffer().is_empty()
+    }
+
+    /// Reads a value of type `T`, panicking if the input has ended.
     pub fn get<T: Cinable>(&mut self) -> T {
         T::read_from(self)
     }
+
+    /// Reads a value of type `T`, or returns `None` if only whitespace is left in the input.
+    ///
+    /// ```
+    /// use hcpl_io::Cin;
+    ///
+    /// let mut cin = Cin::from_reader("1 2\n3".as_bytes());
+    /// let mut sum = 0;
+    /// while let Some(x) = cin.try_get::<u32>() {
+    ///     sum += x;
+    /// }
+    /// assert_eq!(sum, 6);
+    /// ```
+    pub fn try_get<T: Cinable>(&mut self) -> Option<T> {
+        if self.is_eof() {
+            None
+        } else {
+            Some(self.get())
+        }
+    }
+
+    /// Discards whitespace and panics if nothing is left to read.
+    fn expect_token(&mut self) {
+        self.discard_whitespace();
+        if self.buffer().is_empty() {
+            if STRICT {
+                panic!("unexpected end of input at byte {}", self.offset);
+            }
+            panic!("unexpected end of input");
+        }
+    }
+
+    /// Reads a token that must be an integer, with a leading `-` if `signed`,
+    /// and returns it along with its byte offset.
+    fn integer_token(&mut self, signed: bool) -> (Vec<u8>, usize) {
+        self.expect_token();
+        let start = self.offset;
+        let token = self.read_until(|b| b.is_ascii_whitespace());
+
+        let digits = match token.strip_prefix(b"-") {
+            Some(digits) if signed => digits,
+            _ => &token,
+        };
+        if digits.is_empty() || !digits.iter().all(u8::is_ascii_digit) {
+            invalid_token("invalid integer", &token, start);
+        }
+
+        (token, start)
+    }
+
+    /// Reads an integer of type `T`, panicking on malformed or out of range input.
+    fn get_strict<T: std::str::FromStr>(&mut self, signed: bool) -> T {
+        let (token, start) = self.integer_token(signed);
+        match std::str::from_utf8(&token).unwrap().parse() {
+            Ok(x) => x,
+            Err(_) => invalid_token(
+                &format!("integer out of range for {}", std::any::type_name::<T>()),
+                &token,
+                start,
+            ),
+        }
+    }
+}
+
+fn invalid_token(error: &str, token: &[u8], start: usize) -> ! {
+    panic!(
+        "{}: {:?} at byte {}",
+        error,
+        String::from_utf8_lossy(token),
+        start
+    )
 }
 
 pub trait Cinable {
-    fn read_from(cin: &mut Cin) -> Self;
+    fn read_from<R: BufRead, const STRICT: bool>(cin: &mut Cin<R, STRICT>) -> Self;
 }
 
 impl Cinable for char {
-    fn read_from(cin: &mut Cin) -> Self {
-        cin.discard_whitespace();
+    fn read_from<R: BufRead, const STRICT: bool>(cin: &mut Cin<R, STRICT>) -> Self {
+        cin.expect_token();
         let mut ate_one = false;
         cin.read_until(|_| {
             if ate_one {
@@ -91,7 +350,7 @@ impl Cinable for char {
 }
 
 impl Cinable for bool {
-    fn read_from(cin: &mut Cin) -> Self {
+    fn read_from<R: BufRead, const STRICT: bool>(cin: &mut Cin<R, STRICT>) -> Self {
         let c: char = cin.get();
         if c == '1' {
             true
@@ -106,7 +365,6 @@ macro_rules! read_integer_inner {
     ($t:ty, $cin:ident) => {{
         let mut res = 0;
         let mut b = $cin.buffer();
-        debug_assert!(b.len() != 0);
 
         let mut p = 0;
 
@@ -115,9 +373,9 @@ macro_rules! read_integer_inner {
                 $cin.consume(p);
                 p = 0;
                 b = $cin.buffer();
-                debug_assert!(b.len() != 0);
             }
-            !b[p].is_ascii_whitespace()
+            // the number may end exactly at the end of the input
+            !b.is_empty() && !b[p].is_ascii_whitespace()
         } {
             res *= 10;
             res += (b[p] - b'0') as $t;
@@ -131,8 +389,11 @@ macro_rules! read_integer_inner {
 macro_rules! make_unsigned_cinable {
     ($t:ty) => {
         impl Cinable for $t {
-            fn read_from(cin: &mut Cin) -> Self {
-                cin.discard_whitespace();
+            fn read_from<R: BufRead, const STRICT: bool>(cin: &mut Cin<R, STRICT>) -> Self {
+                if STRICT {
+                    return cin.get_strict(false);
+                }
+                cin.expect_token();
                 read_integer_inner!($t, cin)
             }
         }
@@ -142,13 +403,13 @@ macro_rules! make_unsigned_cinable {
 macro_rules! make_signed_cinable {
     ($t:ty) => {
         impl Cinable for $t {
-            fn read_from(cin: &mut Cin) -> Self {
-                cin.discard_whitespace();
-
-                let b = cin.buffer();
-                debug_assert!(b.len() != 0);
+            fn read_from<R: BufRead, const STRICT: bool>(cin: &mut Cin<R, STRICT>) -> Self {
+                if STRICT {
+                    return cin.get_strict(true);
+                }
+                cin.expect_token();
 
-                let neg = if b[0] == b'-' {
+                let neg = if cin.buffer()[0] == b'-' {
                     cin.consume(1);
                     true
                 } else {
@@ -178,3 +439,222 @@ make_signed_cinable!(i32);
 make_signed_cinable!(i64);
 make_signed_cinable!(i128);
 make_signed_cinable!(isize);
+
+/// Splits a token of the form `[+-]digits[.digits][(e|E)[+-]digits]` into
+/// its sign, mantissa and base 10 exponent.
+///
+/// Returns `None` if the token is malformed or the mantissa does not fit in a `u64`.
+fn parse_decimal(token: &[u8]) -> Option<(bool, u64, i32)> {
+    let (neg, rest) = match token.first() {
+        Some(b'-') => (true, &token[1..]),
+        Some(b'+') => (false, &token[1..]),
+        _ => (false, token),
+    };
+
+    let mut mantissa: u64 = 0;
+    let mut exp: i32 = 0;
+    let mut digits = 0;
+    let mut seen_dot = false;
+    let mut p = 0;
+
+    while p < rest.len() {
+        match rest[p] {
+            b @ b'0'..=b'9' => {
+                mantissa = mantissa.checked_mul(10)?.checked_add((b - b'0') as u64)?;
+                digits += 1;
+                if seen_dot {
+                    exp -= 1;
+                }
+            }
+            b'.' if !seen_dot => seen_dot = true,
+            b'e' | b'E' => break,
+            _ => return None,
+        }
+        p += 1;
+    }
+
+    if digits == 0 {
+        return None;
+    }
+
+    if p < rest.len() {
+        let (exp_neg, exp_digits) = match rest.get(p + 1) {
+            Some(b'-') => (true, &rest[p + 2..]),
+            Some(b'+') => (false, &rest[p + 2..]),
+            _ => (false, &rest[p + 1..]),
+        };
+        if exp_digits.is_empty() {
+            return None;
+        }
+        let mut e: i32 = 0;
+        for &b in exp_digits {
+            if !b.is_ascii_digit() {
+                return None;
+            }
+            e = e.saturating_mul(10).saturating_add((b - b'0') as i32);
+        }
+        exp = exp.saturating_add(if exp_neg { -e } else { e });
+    }
+
+    Some((neg, mantissa, exp))
+}
+
+macro_rules! make_float_cinable {
+    ($t:ty, $mantissa_bits:expr, $max_exact_exp:expr) => {
+        impl Cinable for $t {
+            fn read_from<R: BufRead, const STRICT: bool>(cin: &mut Cin<R, STRICT>) -> Self {
+                cin.expect_token();
+                let token = cin.read_until(|b| b.is_ascii_whitespace());
+
+                // Fast path: both the mantissa and the power of ten are exactly
+                // representable, so a single operation gives a correctly rounded result.
+                if let Some((neg, mantissa, exp)) = parse_decimal(&token) {
+                    if mantissa >> $mantissa_bits == 0 && exp.abs() <= $max_exact_exp {
+                        let m = mantissa as $t;
+                        let p = (10 as $t).powi(exp.abs());
+                        let res = if exp < 0 { m / p } else { m * p };
+                        return if neg { -res } else { res };
+                    }
+                }
+
+                std::str::from_utf8(&token).unwrap().parse().unwrap()
+            }
+        }
+    };
+}
+
+make_float_cinable!(f32, 24, 10);
+make_float_cinable!(f64, 53, 22);
+
+/// Reads a whitespace-delimited word.
+impl Cinable for Vec<u8> {
+    fn read_from<R: BufRead, const STRICT: bool>(cin: &mut Cin<R, STRICT>) -> Self {
+        cin.expect_token();
+        cin.read_until(|b| b.is_ascii_whitespace())
+    }
+}
+
+/// Reads a whitespace-delimited word, which must be valid UTF-8.
+impl Cinable for String {
+    fn read_from<R: BufRead, const STRICT: bool>(cin: &mut Cin<R, STRICT>) -> Self {
+        String::from_utf8(cin.get()).unwrap()
+    }
+}
+
+impl<T: Cinable, const N: usize> Cinable for [T; N] {
+    fn read_from<R: BufRead, const STRICT: bool>(cin: &mut Cin<R, STRICT>) -> Self {
+        std::array::from_fn(|_| cin.get())
+    }
+}
+
+macro_rules! make_tuple_cinable {
+    ($($t:ident),*) => {
+        impl<$($t: Cinable),*> Cinable for ($($t,)*) {
+            fn read_from<R: BufRead, const STRICT: bool>(cin: &mut Cin<R, STRICT>) -> Self {
+                ($(cin.get::<$t>(),)*)
+            }
+        }
+    };
+}
+
+make_tuple_cinable!(A);
+make_tuple_cinable!(A, B);
+make_tuple_cinable!(A, B, C);
+make_tuple_cinable!(A, B, C, D);
+make_tuple_cinable!(A, B, C, D, E);
+
+#[cfg(feature = "modnum")]
+mod modnum {
+    use super::{Cin, Cinable};
+    use hcpl_modnum::{DynModnum, DynModnum64, Modnum, Modnum64};
+    use std::io::BufRead;
+
+    /// Reads an integer of any size and sign, reduced modulo `m`.
+    fn read_reduced<R: BufRead, const STRICT: bool>(cin: &mut Cin<R, STRICT>, m: u64) -> u64 {
+        if STRICT {
+            let (token, _) = cin.integer_token(true);
+            let res = token.iter().skip_while(|&&d| d == b'-').fold(0, |res, &d| {
+                ((res as u128 * 10 + (d - b'0') as u128) % m as u128) as u64
+            });
+            return if token[0] == b'-' && res != 0 {
+                m - res
+            } else {
+                res
+            };
+        }
+
+        cin.expect_token();
+
+        let neg = cin.buffer()[0] == b'-';
+        if neg {
+            cin.consume(1);
+        }
+
+        // digits are gathered in chunks of up to 18, so that a single u128
+        // reduction is needed per chunk
+        let mut res = 0;
+        let mut chunk = 0;
+        let mut chunk_pow = 1;
+        let append = |res: u64, chunk: u64, chunk_pow: u64| {
+            ((res as u128 * chunk_pow as u128 + chunk as u128) % m as u128) as u64
+        };
+
+        loop {
+            let b = cin.buffer();
+            let len = b.len();
+            let n = b
+                .iter()
+                .position(|b| b.is_ascii_whitespace())
+                .unwrap_or(len);
+            for &d in &b[..n] {
+                debug_assert!(d.is_ascii_digit(), "unexpected byte {:?}", d as char);
+                chunk = chunk * 10 + (d - b'0') as u64;
+                chunk_pow *= 10;
+                if chunk_pow == 1_000_000_000_000_000_000 {
+                    res = append(res, chunk, chunk_pow);
+                    chunk = 0;
+                    chunk_pow = 1;
+                }
+            }
+            cin.consume(n);
+            if n < len || len == 0 {
+                break;
+            }
+        }
+        res = append(res, chunk, chunk_pow);
+
+        if neg && res != 0 {
+            m - res
+        } else {
+            res
+        }
+    }
+
+    /// Reads an integer of any size and sign, reduced modulo `MOD`.
+    impl<const MOD: u32> Cinable for Modnum<MOD> {
+        fn read_from<R: BufRead, const STRICT: bool>(cin: &mut Cin<R, STRICT>) -> Self {
+            Modnum::new(read_reduced(cin, MOD as u64) as u32)
+        }
+    }
+
+    /// Reads an integer of any size and sign, reduced modulo the current modulus.
+    impl Cinable for DynModnum {
+        fn read_from<R: BufRead, const STRICT: bool>(cin: &mut Cin<R, STRICT>) -> Self {
+            DynModnum::new(read_reduced(cin, DynModnum::modulus() as u64) as u32)
+        }
+    }
+
+    /// Reads an integer of any size and sign, reduced modulo `MOD`.
+    impl<const MOD: u64> Cinable for Modnum64<MOD> {
+        fn read_from<R: BufRead, const STRICT: bool>(cin: &mut Cin<R, STRICT>) -> Self {
+            Modnum64::new(read_reduced(cin, MOD))
+        }
+    }
+
+    /// Reads an integer of any size and sign, reduced modulo the current modulus.
+    impl Cinable for DynModnum64 {
+        fn read_from<R: BufRead, const STRICT: bool>(cin: &mut Cin<R, STRICT>) -> Self {
+            DynModnum64::new(read_reduced(cin, DynModnum64::modulus()))
+        }
+    }
+}
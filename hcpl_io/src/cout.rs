@@ -1,35 +1,56 @@
 use hcpl_integer::Integer;
 use std::io::Write;
 
-pub struct Cout<'a> {
-    buffer: [u8; Cout::BUFFER_SIZE],
+const BUFFER_SIZE: usize = 1 << 17;
+
+/// A buffered writer over any [`Write`] sink, writing to stdout by default.
+///
+/// The buffer is flushed when it is full and when the `Cout` is dropped.
+///
+/// ```
+/// use hcpl_io::Cout;
+///
+/// let mut output = Vec::new();
+/// {
+///     let mut cout = Cout::from_writer(&mut output);
+///     cout.write(1).write(' ').write("two").write('\n');
+/// }
+/// assert_eq!(output, b"1 two\n");
+/// ```
+pub struct Cout<W: Write = std::io::StdoutLock<'static>> {
+    buffer: [u8; BUFFER_SIZE],
     end: usize,
-    stdout: std::io::StdoutLock<'a>,
+    writer: W,
 }
 
-impl<'a> Cout<'a> {
-    const BUFFER_SIZE: usize = 1 << 17;
+impl Cout {
+    pub fn new(stdout: &std::io::Stdout) -> Self {
+        Self::from_writer(stdout.lock())
+    }
+}
 
-    pub fn new(stdout: &'a std::io::Stdout) -> Self {
+impl<W: Write> Cout<W> {
+    /// Creates a `Cout` writing to `writer`, eg. a `&mut Vec<u8>` or a `File`.
+    pub fn from_writer(writer: W) -> Self {
         Self {
-            buffer: [0; Self::BUFFER_SIZE],
+            buffer: [0; BUFFER_SIZE],
             end: 0,
-            stdout: stdout.lock(),
+            writer,
         }
     }
 
     pub fn flush(&mut self) {
-        self.stdout.write_all(&self.buffer[..self.end]).unwrap();
+        self.writer.write_all(&self.buffer[..self.end]).unwrap();
         self.end = 0;
     }
 
     pub fn real_flush(&mut self) {
         self.flush();
-        self.stdout.flush().unwrap();
+        self.writer.flush().unwrap();
     }
 
     pub fn flush_if_too_long(&mut self, n: usize) {
-        if self.end + n > Self::BUFFER_SIZE {
+        if self.end + n > BUFFER_SIZE {
             self.flush()
         }
     }
@@ -40,31 +61,56 @@ impl<'a> Cout<'a> {
     }
 }
 
-impl<'a> Drop for Cout<'a> {
+impl<W: Write> Cout<W> {
+    fn write_bytes(&mut self, bytes: &[u8]) {
+        for chunk in bytes.chunks(BUFFER_SIZE) {
+            self.flush_if_too_long(chunk.len());
+            self.buffer[self.end..self.end + chunk.len()].copy_from_slice(chunk);
+            self.end += chunk.len();
+        }
+    }
+}
+
+impl<W: Write> std::fmt::Write for Cout<W> {
+    fn write_str(&mut self, s: &str) -> std::fmt::Result {
+        self.write_bytes(s.as_bytes());
+        Ok(())
+    }
+}
+
+impl<W: Write> Drop for Cout<W> {
     fn drop(&mut self) {
         self.flush();
     }
 }
 
 pub trait Coutable {
-    fn write_to(&self, cout: &mut Cout);
+    fn write_to<W: Write>(&self, cout: &mut Cout<W>);
 }
 
 impl Coutable for char {
-    fn write_to(&self, cout: &mut Cout) {
+    fn write_to<W: Write>(&self, cout: &mut Cout<W>) {
         cout.flush_if_too_long(1);
         cout.buffer[cout.end] = *self as u8;
         cout.end += 1;
     }
 }
 
-impl Coutable for &str {
-    fn write_to(&self, cout: &mut Cout) {
-        for chunk in self.as_bytes().chunks(Cout::BUFFER_SIZE) {
-            cout.flush_if_too_long(chunk.len());
-            cout.buffer[cout.end..cout.end + chunk.len()].copy_from_slice(chunk);
-            cout.end += chunk.len();
-        }
+impl<T: Coutable + ?Sized> Coutable for &T {
+    fn write_to<W: Write>(&self, cout: &mut Cout<W>) {
+        (**self).write_to(cout);
+    }
+}
+
+impl Coutable for str {
+    fn write_to<W: Write>(&self, cout: &mut Cout<W>) {
+        cout.write_bytes(self.as_bytes());
+    }
+}
+
+impl Coutable for String {
+    fn write_to<W: Write>(&self, cout: &mut Cout<W>) {
+        cout.write_bytes(self.as_bytes());
     }
 }
 
@@ -87,7 +133,7 @@ macro_rules! write_integer_inner {
 macro_rules! make_unsigned_coutable {
     ($t:ty) => {
         impl Coutable for $t {
-            fn write_to(&self, cout: &mut Cout) {
+            fn write_to<W: Write>(&self, cout: &mut Cout<W>) {
                 let mut rest = *self;
                 let mut buf = [0u8; <$t as Integer>::BASE_10_MAX_LENGTH];
                 let mut end = buf.len();
@@ -104,7 +150,7 @@ macro_rules! make_unsigned_coutable {
 macro_rules! make_signed_coutable {
     ($t:ty) => {
         impl Coutable for $t {
-            fn write_to(&self, cout: &mut Cout) {
+            fn write_to<W: Write>(&self, cout: &mut Cout<W>) {
                 let neg = *self < 0;
                 let mut rest = self.overflowing_abs().0 as <$t as Integer>::AsUnsigned;
                 let mut buf = [0u8; <$t as Integer>::BASE_10_MAX_LENGTH];
@@ -136,3 +182,253 @@ make_signed_coutable!(i32);
 make_signed_coutable!(i64);
 make_signed_coutable!(i128);
 make_signed_coutable!(isize);
+
+macro_rules! make_float_coutable {
+    ($t:ty) => {
+        impl Coutable for $t {
+            fn write_to<W: Write>(&self, cout: &mut Cout<W>) {
+                std::fmt::Write::write_fmt(cout, format_args!("{}", self)).unwrap();
+            }
+        }
+    };
+}
+
+make_float_coutable!(f32);
+make_float_coutable!(f64);
+
+/// Writes `self.0` with exactly `self.1` digits after the decimal point.
+///
+/// The output is correctly rounded, with ties to even, so it matches
+/// `format!("{:.*}", self.1, self.0)`.
+///
+/// ```
+/// use hcpl_io::{Cout, Fixed};
+///
+/// let mut output = Vec::new();
+/// Cout::from_writer(&mut output).write(Fixed(-2.0 / 3.0, 9));
+/// assert_eq!(output, b"-0.666666667");
+///
+/// let fixed = |x: f64, precision: usize| {
+///     let mut output = Vec::new();
+///     Cout::from_writer(&mut output).write(Fixed(x, precision));
+///     String::from_utf8(output).unwrap()
+/// };
+/// // 0.5175 is stored as slightly less than itself
+/// assert_eq!(fixed(0.5175, 3), "0.517");
+/// assert_eq!(fixed(0.24776562500000002, 8), "0.24776563");
+/// assert_eq!(fixed(0.125, 2), "0.12");
+/// assert_eq!(fixed(2.5, 0), "2");
+/// assert_eq!(fixed(1e-300, 18), "0.000000000000000000");
+///
+/// let mut state = 0x2545_f491_4f6c_dd1du64;
+/// for _ in 0..20000 {
+///     state ^= state << 13;
+///     state ^= state >> 7;
+///     state ^= state << 17;
+///     let x = (state >> 11) as f64 / (1u64 << 53) as f64 * 10f64.powi((state % 12) as i32);
+///     for precision in 0..=18 {
+///         assert_eq!(fixed(x, precision), format!("{:.*}", precision, x));
+///         assert_eq!(fixed(-x, precision), format!("{:.*}", precision, -x));
+///     }
+/// }
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Fixed(pub f64, pub usize);
+
+/// Splits a finite non-negative `x < 2^64` into its integer part and its
+/// fractional part scaled by `scale`, rounded exactly with ties to even.
+fn split_fixed(x: f64, scale: u64) -> (u64, u64) {
+    let bits = x.to_bits();
+    let biased_exp = (bits >> 52) as i32 & 0x7ff;
+    let fraction = bits & ((1 << 52) - 1);
+
+    // x = m * 2^e exactly
+    let (m, e) = if biased_exp == 0 {
+        (fraction, -1074)
+    } else {
+        (fraction | 1 << 52, biased_exp - 1075)
+    };
+    if e >= 0 {
+        return (m << e, 0);
+    }
+
+    let shift = -e as u32;
+    let (int, rem) = if shift < 64 {
+        (m >> shift, m & ((1 << shift) - 1))
+    } else {
+        (0, m)
+    };
+
+    // the fractional part is rem / 2^shift, and rem * scale < 2^113
+    if shift >= 128 {
+        return (int, 0);
+    }
+    let scaled = rem as u128 * scale as u128;
+    let mut frac = (scaled >> shift) as u64;
+    let low = scaled & ((1 << shift) - 1);
+    let half = 1 << (shift - 1);
+    if low > half || (low == half && frac & 1 == 1) {
+        frac += 1;
+    }
+    (int, frac)
+}
+
+impl Coutable for Fixed {
+    fn write_to<W: Write>(&self, cout: &mut Cout<W>) {
+        let Fixed(x, precision) = *self;
+
+        // outside of this range the integer and fractional parts may not fit in a u64
+        if !x.is_finite() || x.abs() >= 1e18 || precision > 18 {
+            std::fmt::Write::write_fmt(cout, format_args!("{:.*}", precision, x)).unwrap();
+            return;
+        }
+
+        let scale = 10u64.pow(precision as u32);
+        let (mut int, mut frac) = split_fixed(x.abs(), scale);
+        if frac >= scale {
+            int += 1;
+            frac -= scale;
+        }
+
+        if x.is_sign_negative() {
+            cout.write('-');
+        }
+        cout.write(int);
+
+        if precision != 0 {
+            let mut buf = [b'0'; 19];
+            buf[0] = b'.';
+            for i in (1..=precision).rev() {
+                buf[i] = (frac % 10) as u8 + b'0';
+                frac /= 10;
+            }
+            cout.write_bytes(&buf[..=precision]);
+        }
+    }
+}
+
+/// Writes the elements separated by spaces.
+impl<T: Coutable> Coutable for [T] {
+    fn write_to<W: Write>(&self, cout: &mut Cout<W>) {
+        Joined(self, ' ').write_to(cout);
+    }
+}
+
+/// Writes the elements separated by spaces.
+impl<T: Coutable> Coutable for Vec<T> {
+    fn write_to<W: Write>(&self, cout: &mut Cout<W>) {
+        self.as_slice().write_to(cout);
+    }
+}
+
+/// Writes the value, or `-1` if there is none.
+impl<T: Coutable> Coutable for Option<T> {
+    fn write_to<W: Write>(&self, cout: &mut Cout<W>) {
+        match self {
+            Some(x) => x.write_to(cout),
+            None => cout.write_bytes(b"-1"),
+        }
+    }
+}
+
+macro_rules! make_tuple_coutable {
+    ($head:ident $(, $tail:ident)*) => {
+        /// Writes the elements separated by spaces.
+        impl<$head: Coutable $(, $tail: Coutable)*> Coutable for ($head, $($tail,)*) {
+            #[allow(non_snake_case)]
+            fn write_to<W: Write>(&self, cout: &mut Cout<W>) {
+                let ($head, $($tail,)*) = self;
+                $head.write_to(cout);
+                $(
+                    cout.write(' ');
+                    $tail.write_to(cout);
+                )*
+            }
+        }
+    };
+}
+
+make_tuple_coutable!(A);
+make_tuple_coutable!(A, B);
+make_tuple_coutable!(A, B, C);
+make_tuple_coutable!(A, B, C, D);
+make_tuple_coutable!(A, B, C, D, E);
+
+/// Writes the items of `self.0` separated by `self.1`.
+///
+/// ```
+/// use hcpl_io::{Cout, Joined, Lines};
+///
+/// let mut output = Vec::new();
+/// {
+///     let mut cout = Cout::from_writer(&mut output);
+///     cout.write(Joined(&[1, 2, 3], ", ")).write('\n');
+///     cout.write(Lines([(1, 'a'), (2, 'b')].iter()));
+/// }
+/// assert_eq!(output, b"1, 2, 3\n1 a\n2 b");
+/// ```
+#[derive(Copy, Clone, Debug)]
+pub struct Joined<I, S>(pub I, pub S);
+
+impl<I, S> Coutable for Joined<I, S>
+where
+    I: IntoIterator + Clone,
+    I::Item: Coutable,
+    S: Coutable,
+{
+    fn write_to<W: Write>(&self, cout: &mut Cout<W>) {
+        let mut iter = self.0.clone().into_iter();
+        if let Some(first) = iter.next() {
+            first.write_to(cout);
+            for x in iter {
+                self.1.write_to(cout);
+                x.write_to(cout);
+            }
+        }
+    }
+}
+
+/// Writes the items of `self.0` on separate lines.
+#[derive(Copy, Clone, Debug)]
+pub struct Lines<I>(pub I);
+
+impl<I> Coutable for Lines<I>
+where
+    I: IntoIterator + Clone,
+    I::Item: Coutable,
+{
+    fn write_to<W: Write>(&self, cout: &mut Cout<W>) {
+        Joined(self.0.clone(), '\n').write_to(cout);
+    }
+}
+
+#[cfg(feature = "modnum")]
+mod modnum {
+    use super::{Cout, Coutable};
+    use hcpl_modnum::{DynModnum, DynModnum64, Modnum, Modnum64};
+    use std::io::Write;
+
+    impl<const MOD: u32> Coutable for Modnum<MOD> {
+        fn write_to<W: Write>(&self, cout: &mut Cout<W>) {
+            cout.write(u32::from(*self));
+        }
+    }
+
+    impl Coutable for DynModnum {
+        fn write_to<W: Write>(&self, cout: &mut Cout<W>) {
+            cout.write(u32::from(*self));
+        }
+    }
+
+    impl<const MOD: u64> Coutable for Modnum64<MOD> {
+        fn write_to<W: Write>(&self, cout: &mut Cout<W>) {
+            cout.write(u64::from(*self));
+        }
+    }
+
+    impl Coutable for DynModnum64 {
+        fn write_to<W: Write>(&self, cout: &mut Cout<W>) {
+            cout.write(u64::from(*self));
+        }
+    }
+}
@@ -3,15 +3,21 @@ macro_rules! _io__input_macro__make {
     ($cin:ident, $dol:tt) => {
         #[allow(unused_macros)]
         macro_rules! input {
-            ($dol ($dol tail:tt)*) => {
-                hcpl_io::input_macro::input_from!($cin; $dol ($dol tail)*)
-            };
+            ($dol ($dol tail:tt)*) => { hcpl_io::input_macro::input_from!($cin; $dol ($dol tail)*) };
         }
     };
 }
 
 #[macro_export]
 macro_rules! _io__input_macro__input_from {
+    ($cin:ident; $var:ident : grid($h:expr, $w:expr), $($tail:tt)*) => {
+        let $var = hcpl_io::input_macro::read_value!($cin; grid($h, $w));
+        hcpl_io::input_macro::input_from!($cin; $($tail)*);
+    };
+    ($cin:ident; mut $var:ident : grid($h:expr, $w:expr), $($tail:tt)*) => {
+        let mut $var = hcpl_io::input_macro::read_value!($cin; grid($h, $w));
+        hcpl_io::input_macro::input_from!($cin; $($tail)*);
+    };
     ($cin:ident; $var:ident : $type:tt $(+ $offset:literal)? $(- $noffset:literal)?, $($tail:tt)*) => {
         let $var = hcpl_io::input_macro::read_value!($cin; $type $(+ $offset)? $(- $noffset)?);
         hcpl_io::input_macro::input_from!($cin; $($tail)*);
@@ -29,11 +35,26 @@ macro_rules! _io__input_macro__read_value {
         ( $(hcpl_io::input_macro::read_value!($cin; $inner $(+ $offset)? $(- $noffset)?)),* )
     };
     ($cin:ident; [ $inner:tt $(+ $offset:literal)? $(- $noffset:literal)?; ($($ns:expr),*) ]) => {
-        hcpl_tensor::Tensor::from_iter_and_dims(
+        $crate::Tensor::from_iter_and_dims(
             std::iter::repeat_with(|| hcpl_io::input_macro::read_value!($cin; $inner $(+ $offset)? $(- $noffset)?)),
             [$($ns),*],
         )
     };
+    ($cin:ident; [ line; .. ]) => {
+        std::iter::from_fn(|| $cin.try_read_line())
+            .map(|line| String::from_utf8(line).unwrap())
+            .collect::<Vec<_>>()
+    };
+    ($cin:ident; [ $inner:tt $(+ $offset:literal)? $(- $noffset:literal)?; .. ]) => {
+        std::iter::from_fn(|| {
+            if $cin.is_eof() {
+                None
+            } else {
+                Some(hcpl_io::input_macro::read_value!($cin; $inner $(+ $offset)? $(- $noffset)?))
+            }
+        })
+        .collect::<Vec<_>>()
+    };
     ($cin:ident; [ $inner:tt $(+ $offset:literal)? $(- $noffset:literal)?; $n:expr ]) => {
         (0..$n).map(|_| hcpl_io::input_macro::read_value!($cin; $inner $(+ $offset)? $(- $noffset)?)).collect::<Vec<_>>()
     };
@@ -41,6 +62,25 @@ macro_rules! _io__input_macro__read_value {
         $cin.discard_whitespace();
         $cin.read_until(|b| b.is_ascii_whitespace())
     }};
+    ($cin:ident; chars) => {
+        $cin.get::<String>().chars().collect::<Vec<char>>()
+    };
+    ($cin:ident; line) => {
+        String::from_utf8($cin.read_line()).unwrap()
+    };
+    ($cin:ident; grid($h:expr, $w:expr)) => {{
+        let (h, w) = ($h, $w);
+        let mut cells = Vec::with_capacity(h * w);
+        for _ in 0..h {
+            let row = $cin.get::<Vec<u8>>();
+            assert_eq!(row.len(), w, "grid row has the wrong width");
+            cells.extend(row);
+        }
+        $crate::Tensor::<u8, 2>::from_vec(cells, [h, w])
+    }};
+    ($cin:ident; usize1) => {
+        $cin.get::<usize>().checked_sub(1).expect("usize1: expected a 1-based index")
+    };
     ($cin:ident; digit) => {{
         let digit = $cin.get::<char>() as u8;
         debug_assert!(b'0' <= digit && digit <= b'9', "digit = {}", digit as char);
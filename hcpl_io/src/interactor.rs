@@ -0,0 +1,136 @@
+//! An in-process harness for testing solutions to interactive problems.
+//!
+//! ```
+//! use hcpl_io::{interactor, Cin, Cout};
+//!
+//! // The judge picks a number and answers `<`, `>` or `=` to each guess.
+//! let guesses = interactor::run(
+//!     |mut cin, mut cout| {
+//!         let (mut lo, mut hi) = (1, 100);
+//!         loop {
+//!             let mid = (lo + hi) / 2;
+//!             cout.write(mid).write('\n').real_flush();
+//!             match cin.get::<char>() {
+//!                 '<' => hi = mid - 1,
+//!                 '>' => lo = mid + 1,
+//!                 _ => break,
+//!             }
+//!         }
+//!     },
+//!     |mut cin, mut cout| {
+//!         let secret = 42;
+//!         let mut guesses = 0;
+//!         while let Some(x) = cin.try_get::<u32>() {
+//!             guesses += 1;
+//!             let answer = if secret < x { '<' } else if secret > x { '>' } else { '=' };
+//!             cout.write(answer).write('\n').real_flush();
+//!         }
+//!         guesses
+//!     },
+//! );
+//! assert!(guesses <= 7);
+//! ```
+
+use super::{Cin, Cout};
+use std::io::{BufRead, Read, Write};
+use std::sync::mpsc::{channel, Receiver, Sender};
+
+/// The reading end of a [`pipe`].
+pub struct PipeReader {
+    receiver: Receiver<Vec<u8>>,
+    buffer: Vec<u8>,
+    pos: usize,
+}
+
+/// The writing end of a [`pipe`]. Every `write` is sent to the reader immediately.
+pub struct PipeWriter {
+    sender: Sender<Vec<u8>>,
+}
+
+/// Creates an in-memory byte pipe between two threads.
+///
+/// Reading blocks until data is available, and reports the end of input
+/// once the `PipeWriter` is dropped.
+pub fn pipe() -> (PipeWriter, PipeReader) {
+    let (sender, receiver) = channel();
+    (
+        PipeWriter { sender },
+        PipeReader {
+            receiver,
+            buffer: Vec::new(),
+            pos: 0,
+        },
+    )
+}
+
+impl Read for PipeReader {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(buf.len());
+        buf[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl BufRead for PipeReader {
+    fn fill_buf(&mut self) -> std::io::Result<&[u8]> {
+        while self.pos == self.buffer.len() {
+            match self.receiver.recv() {
+                Ok(buffer) => {
+                    self.buffer = buffer;
+                    self.pos = 0;
+                }
+                Err(_) => break,
+            }
+        }
+        Ok(&self.buffer[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.pos += amt;
+    }
+}
+
+impl Write for PipeWriter {
+    fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+        // the reader hanging up is not an error for the writer, like a closed judge
+        let _ = self.sender.send(buf.to_vec());
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> std::io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Runs `solution` on the current thread against `judge` on another thread,
+/// with the output of each one wired to the input of the other, and returns
+/// the result of `judge`.
+///
+/// Both sides must `real_flush` their `Cout` before waiting for an answer,
+/// or the interaction deadlocks. Dropping a `Cout` flushes it and ends the
+/// input of the other side.
+pub fn run<S, J, T>(solution: S, judge: J) -> T
+where
+    S: FnOnce(Cin<PipeReader>, Cout<PipeWriter>),
+    J: FnOnce(Cin<PipeReader>, Cout<PipeWriter>) -> T + Send,
+    T: Send,
+{
+    let (to_judge, judge_input) = pipe();
+    let (to_solution, solution_input) = pipe();
+
+    std::thread::scope(|scope| {
+        let judge = scope.spawn(move || {
+            judge(
+                Cin::from_reader(judge_input),
+                Cout::from_writer(to_solution),
+            )
+        });
+        solution(
+            Cin::from_reader(solution_input),
+            Cout::from_writer(to_judge),
+        );
+        judge.join().unwrap()
+    })
+}
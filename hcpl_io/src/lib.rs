@@ -1,25 +1,48 @@
 mod cin;
 mod cout;
 pub mod input_macro;
+pub mod interactor;
 pub mod output_macro;
-pub use cin::{Cin, Cinable};
-pub use cout::{Cout, Coutable};
+pub use cin::{Cin, Cinable, SliceReader};
+pub use cout::{Cout, Coutable, Fixed, Joined, Lines};
+/// The type of `[T; (n, m)]` and `grid(h, w)` values read by `input!`.
+pub use hcpl_tensor::Tensor;
 
+/// Creates `$cin` and `$cout` over stdin and stdout, along with the
+/// `input!`, `out!` and `outln!` macros.
+///
+/// With `prelude!(cin, cout; interactive)`, every `outln!` also flushes
+/// stdout, as required by interactive judges.
+///
+/// With `prelude!(cin, cout; slurp)`, all of stdin is read up front with
+/// [`Cin::slurp`], which is faster for huge inputs.
 #[macro_export]
 macro_rules! _io__prelude {
     ($cin:ident, $cout:ident) => {
+        hcpl_io::prelude!(@handles $cin, $cout, new);
+        hcpl_io::input_macro::make!($cin, $);
+        hcpl_io::output_macro::make!($cout, $);
+    };
+    ($cin:ident, $cout:ident; interactive) => {
+        hcpl_io::prelude!(@handles $cin, $cout, new);
+        hcpl_io::input_macro::make!($cin, $);
+        hcpl_io::output_macro::make_interactive!($cout, $);
+    };
+    ($cin:ident, $cout:ident; slurp) => {
+        hcpl_io::prelude!(@handles $cin, $cout, slurp);
+        hcpl_io::input_macro::make!($cin, $);
+        hcpl_io::output_macro::make!($cout, $);
+    };
+    (@handles $cin:ident, $cout:ident, $new_cin:ident) => {
         let stdin_handle = ::std::io::stdin();
         let stdout_handle = ::std::io::stdout();
 
         #[allow(unused_variables)]
         #[allow(unused_mut)]
-        let mut $cin = hcpl_io::Cin::new(&stdin_handle);
+        let mut $cin = hcpl_io::Cin::$new_cin(&stdin_handle);
         #[allow(unused_variables)]
         #[allow(unused_mut)]
         let mut $cout = hcpl_io::Cout::new(&stdout_handle);
-
-        hcpl_io::input_macro::make!($cin, $);
-        hcpl_io::output_macro::make!($cout, $);
     };
 }
 
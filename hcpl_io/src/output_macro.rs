@@ -3,15 +3,29 @@ macro_rules! _io__output_macro__make {
     ($cout:ident, $dol:tt) => {
         #[allow(unused_macros)]
         macro_rules! out {
-            ($dol ($dol tail:tt)*) => {
-                hcpl_io::output_macro::out_to!($cout; $dol ($dol tail)*)
-            };
+            ($dol ($dol tail:tt)*) => { hcpl_io::output_macro::out_to!($cout; $dol ($dol tail)*) };
         }
         #[allow(unused_macros)]
         macro_rules! outln {
-            ($dol ($dol tail:tt)*) => {
-                hcpl_io::output_macro::out_to!($cout; $dol ($dol tail)*; '\n')
-            };
+            ($dol ($dol tail:tt)*) => { hcpl_io::output_macro::out_to!($cout; $dol ($dol tail)*; '\n') };
+        }
+    };
+}
+
+/// Like [`make`], but `outln!` also flushes `$cout` for interactive problems.
+#[macro_export]
+macro_rules! _io__output_macro__make_interactive {
+    ($cout:ident, $dol:tt) => {
+        #[allow(unused_macros)]
+        macro_rules! out {
+            ($dol ($dol tail:tt)*) => { hcpl_io::output_macro::out_to!($cout; $dol ($dol tail)*) };
+        }
+        #[allow(unused_macros)]
+        macro_rules! outln {
+            ($dol ($dol tail:tt)*) => {{
+                hcpl_io::output_macro::out_to!($cout; $dol ($dol tail)*; '\n');
+                $cout.real_flush();
+            }};
         }
     };
 }
@@ -32,4 +46,7 @@ macro_rules! _io__output_macro__out_to {
     ($cout:ident;) => {};
 }
 
-pub use crate::{_io__output_macro__make as make, _io__output_macro__out_to as out_to};
+pub use crate::{
+    _io__output_macro__make as make, _io__output_macro__make_interactive as make_interactive,
+    _io__output_macro__out_to as out_to,
+};
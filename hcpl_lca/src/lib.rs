@@ -0,0 +1,174 @@
+use hcpl_rmq::OwnedRMQ;
+
+/// Lowest common ancestor queries on a rooted tree in O(1), using a range
+/// minimum query over its Euler tour.
+///
+/// Vertices are numbered `0..n` and the tree is traversed without recursion.
+///
+/// ```
+/// use hcpl_lca::Lca;
+///
+/// //     0
+/// //    / \
+/// //   1   2
+/// //  / \
+/// // 3   4
+/// let lca = Lca::from_parents(&[0, 0, 0, 1, 1], 0);
+/// assert_eq!(lca.lca(3, 4), 1);
+/// assert_eq!(lca.lca(4, 2), 0);
+/// assert_eq!(lca.dist(3, 2), 3);
+/// assert!(lca.is_ancestor(1, 4));
+/// assert_eq!(lca.kth_ancestor(4, 2), Some(0));
+/// assert_eq!(lca.kth_ancestor(4, 3), None);
+///
+/// assert!(Lca::from_parents(&[], 0).is_empty());
+/// ```
+pub struct Lca {
+    parent: Vec<usize>,
+    depth: Vec<usize>,
+    /// Index of the first occurrence of each vertex in the Euler tour,
+    /// which also orders the vertices in pre-order.
+    first: Vec<usize>,
+    /// Index of the last occurrence of each vertex in the Euler tour.
+    last: Vec<usize>,
+    /// The vertices of each depth, in pre-order.
+    by_depth: Vec<Vec<usize>>,
+    /// The Euler tour as `(depth, vertex)` pairs.
+    tour: OwnedRMQ<(usize, usize)>,
+}
+
+impl Lca {
+    /// Builds the structure from the adjacency lists of an undirected tree,
+    /// rooted at `root`, in O(n).
+    ///
+    /// `root` is ignored if the tree has no vertices.
+    pub fn from_adjacency(adj: &[Vec<usize>], root: usize) -> Self {
+        let n = adj.len();
+        if n == 0 {
+            return Self {
+                parent: Vec::new(),
+                depth: Vec::new(),
+                first: Vec::new(),
+                last: Vec::new(),
+                by_depth: Vec::new(),
+                tour: OwnedRMQ::new(Vec::new()),
+            };
+        }
+
+        let mut parent = vec![usize::MAX; n];
+        let mut depth = vec![0; n];
+        let mut first = vec![usize::MAX; n];
+        let mut last = vec![usize::MAX; n];
+        let mut by_depth = vec![Vec::new()];
+        let mut tour = Vec::with_capacity(2 * n);
+
+        first[root] = 0;
+        by_depth[0].push(root);
+        tour.push((0, root));
+
+        // (vertex, index of the next neighbour to visit)
+        let mut stack = vec![(root, 0)];
+        while let Some((v, i)) = stack.last_mut() {
+            let v = *v;
+            if let Some(&u) = adj[v].get(*i) {
+                *i += 1;
+                if u == parent[v] {
+                    continue;
+                }
+
+                parent[u] = v;
+                depth[u] = depth[v] + 1;
+                first[u] = tour.len();
+                if by_depth.len() == depth[u] {
+                    by_depth.push(Vec::new());
+                }
+                by_depth[depth[u]].push(u);
+                tour.push((depth[u], u));
+                stack.push((u, 0));
+            } else {
+                last[v] = tour.len() - 1;
+                stack.pop();
+                if let Some(&(p, _)) = stack.last() {
+                    tour.push((depth[p], p));
+                }
+            }
+        }
+
+        assert!(
+            first.iter().all(|&i| i != usize::MAX),
+            "the tree is not connected"
+        );
+
+        parent[root] = root;
+        Self {
+            parent,
+            depth,
+            first,
+            last,
+            by_depth,
+            tour: OwnedRMQ::new(tour),
+        }
+    }
+
+    /// Builds the structure from the parent of each vertex, in O(n).
+    /// `parents[root]` is ignored.
+    pub fn from_parents(parents: &[usize], root: usize) -> Self {
+        let mut children = vec![Vec::new(); parents.len()];
+        for (v, &p) in parents.iter().enumerate() {
+            if v != root {
+                children[p].push(v);
+            }
+        }
+        Self::from_adjacency(&children, root)
+    }
+
+    /// Returns the number of vertices.
+    pub fn len(&self) -> usize {
+        self.depth.len()
+    }
+
+    /// Returns `true` if the tree has no vertices.
+    pub fn is_empty(&self) -> bool {
+        self.depth.is_empty()
+    }
+
+    /// Returns the parent of `v`, or `None` if `v` is the root.
+    pub fn parent(&self, v: usize) -> Option<usize> {
+        Some(self.parent[v]).filter(|&p| p != v)
+    }
+
+    /// Returns the number of edges between `v` and the root.
+    pub fn depth(&self, v: usize) -> usize {
+        self.depth[v]
+    }
+
+    /// Returns the lowest common ancestor of `u` and `v` in O(1).
+    pub fn lca(&self, u: usize, v: usize) -> usize {
+        let (i, j) = if self.first[u] <= self.first[v] {
+            (self.first[u], self.first[v])
+        } else {
+            (self.first[v], self.first[u])
+        };
+        self.tour.min(i..=j).1
+    }
+
+    /// Returns the number of edges on the path between `u` and `v` in O(1).
+    pub fn dist(&self, u: usize, v: usize) -> usize {
+        self.depth[u] + self.depth[v] - 2 * self.depth[self.lca(u, v)]
+    }
+
+    /// Returns `true` if `u` is an ancestor of `v`, including `u == v`, in O(1).
+    pub fn is_ancestor(&self, u: usize, v: usize) -> bool {
+        self.first[u] <= self.first[v] && self.last[v] <= self.last[u]
+    }
+
+    /// Returns the ancestor of `v` that is `k` edges above it, or `None` if
+    /// `k` exceeds the depth of `v`, in O(log n).
+    pub fn kth_ancestor(&self, v: usize, k: usize) -> Option<usize> {
+        let d = self.depth[v].checked_sub(k)?;
+        // the ancestor is the last vertex of depth `d` before `v` in pre-order
+        let candidates = &self.by_depth[d];
+        let i = candidates.partition_point(|&w| self.first[w] <= self.first[v]);
+        Some(candidates[i - 1])
+    }
+}
@@ -23,28 +23,195 @@ pub fn solve<S: State>(n: u32, queries: &[(u32, u32)], mut state: S) -> Vec<S::O
 
     for i in indices {
         let (target_l, target_r) = queries[i];
-        while r < target_r {
-            state.insert(r);
-            r += 1;
-        }
-        while l > target_l {
-            l -= 1;
-            state.insert(l);
-        }
-        while r > target_r {
-            r -= 1;
-            state.erase(r);
+        move_range(&mut state, &mut l, &mut r, target_l, target_r);
+        answer[i] = Some(state.get());
+    }
+
+    answer.into_iter().map(|a| a.unwrap()).collect()
+}
+
+/// A [`State`] that also supports point updates, for [`solve_with_updates`].
+///
+/// Updates are applied and undone like a stack: `apply(k)` is only called
+/// when exactly the updates `0..k` are applied, and `undo(k)` only when
+/// exactly the updates `0..=k` are. The element an update changes is never
+/// in the current range while it is applied or undone, since the driver
+/// erases it before and inserts it back after, so only the underlying
+/// array needs to change. Storing the new value of each update and swapping
+/// it with the array element implements both `apply` and `undo`.
+pub trait UpdateState: State {
+    /// Applies update `k`. The element it changes is never in the current range.
+    fn apply(&mut self, k: u32);
+    /// Reverts update `k`. The element it changes is never in the current range.
+    fn undo(&mut self, k: u32);
+}
+
+/// Mo's algorithm with updates, answering each query `(l, r, t)` on the
+/// range `l..r` after the first `t` updates, where update `k` changes
+/// the element at position `updates[k]`.
+///
+/// Queries are sorted by the blocks of `l` and `r`, then by `t`. With `q`
+/// queries and `u` updates, blocks of size `B` cost O(q B) moves of `l`
+/// and `r` within blocks, O(n^2 / B) moves of `r` across blocks and
+/// O(u n^2 / B^2) moves of `t`. The block size is
+/// `B = max(cbrt(n^2 u / q), n / sqrt(q), 1)`, which balances the first and
+/// the last term, and falls back to the block size of plain Mo's algorithm
+/// when there are few updates. This uses O(n^(5/3)) insertions, deletions
+/// and updates when the number of queries and updates are both O(n).
+///
+/// ```
+/// use hcpl_mo::{solve_with_updates, State, UpdateState};
+///
+/// // the number of distinct values in a range, under point assignments
+/// struct Distinct {
+///     a: Vec<usize>,
+///     // the position and new value of each update, swapped with the array
+///     // element when the update is applied or undone
+///     updates: Vec<(u32, usize)>,
+///     count: Vec<u32>,
+///     distinct: usize,
+/// }
+///
+/// impl State for Distinct {
+///     type Output = usize;
+///
+///     fn insert(&mut self, i: u32) {
+///         let c = &mut self.count[self.a[i as usize]];
+///         if *c == 0 {
+///             self.distinct += 1;
+///         }
+///         *c += 1;
+///     }
+///
+///     fn erase(&mut self, i: u32) {
+///         let c = &mut self.count[self.a[i as usize]];
+///         *c -= 1;
+///         if *c == 0 {
+///             self.distinct -= 1;
+///         }
+///     }
+///
+///     fn get(&self) -> usize {
+///         self.distinct
+///     }
+/// }
+///
+/// impl UpdateState for Distinct {
+///     fn apply(&mut self, k: u32) {
+///         let (i, x) = &mut self.updates[k as usize];
+///         std::mem::swap(&mut self.a[*i as usize], x);
+///     }
+///
+///     fn undo(&mut self, k: u32) {
+///         self.apply(k);
+///     }
+/// }
+///
+/// let updates = vec![(2, 2), (0, 3)]; // a[2] = 2, then a[0] = 3
+/// let positions: Vec<u32> = updates.iter().map(|&(i, _)| i).collect();
+/// let state = Distinct {
+///     a: vec![1, 2, 1, 3],
+///     updates,
+///     count: vec![0; 4],
+///     distinct: 0,
+/// };
+///
+/// let queries = [(0, 4, 0), (0, 3, 1), (0, 4, 2), (0, 1, 2), (2, 4, 0)];
+/// let answers = solve_with_updates(4, &positions, &queries, state);
+/// assert_eq!(answers, [3, 2, 2, 1, 2]);
+/// ```
+pub fn solve_with_updates<S: UpdateState>(
+    n: u32,
+    updates: &[u32],
+    queries: &[(u32, u32, u32)],
+    mut state: S,
+) -> Vec<S::Output> {
+    let q = queries.len().max(1) as f64;
+    let n_f = n.max(1) as f64;
+    let block = (n_f * n_f * updates.len() as f64 / q)
+        .cbrt()
+        .max(n_f / q.sqrt())
+        .max(1.0) as u32;
+
+    let mut indices: Vec<usize> = (0..queries.len()).collect();
+    indices.sort_by_cached_key(|&i| {
+        let (x, y, t) = queries[i];
+        time_order(x, y, t, block)
+    });
+
+    let mut answer = Vec::<Option<S::Output>>::with_capacity(queries.len());
+    for _ in 0..queries.len() {
+        answer.push(None);
+    }
+
+    let mut l = 0;
+    let mut r = 0;
+    let mut t = 0;
+
+    for i in indices {
+        let (target_l, target_r, target_t) = queries[i];
+        move_range(&mut state, &mut l, &mut r, target_l, target_r);
+
+        while t < target_t {
+            let inside = (l..r).contains(&updates[t as usize]);
+            if inside {
+                state.erase(updates[t as usize]);
+            }
+            state.apply(t);
+            if inside {
+                state.insert(updates[t as usize]);
+            }
+            t += 1;
         }
-        while l < target_l {
-            state.erase(l);
-            l += 1;
+        while t > target_t {
+            t -= 1;
+            let inside = (l..r).contains(&updates[t as usize]);
+            if inside {
+                state.erase(updates[t as usize]);
+            }
+            state.undo(t);
+            if inside {
+                state.insert(updates[t as usize]);
+            }
         }
+
         answer[i] = Some(state.get());
     }
 
     answer.into_iter().map(|a| a.unwrap()).collect()
 }
 
+fn move_range<S: State>(state: &mut S, l: &mut u32, r: &mut u32, target_l: u32, target_r: u32) {
+    while *r < target_r {
+        state.insert(*r);
+        *r += 1;
+    }
+    while *l > target_l {
+        *l -= 1;
+        state.insert(*l);
+    }
+    while *r > target_r {
+        *r -= 1;
+        state.erase(*r);
+    }
+    while *l < target_l {
+        state.erase(*l);
+        *l += 1;
+    }
+}
+
+/// Sort key for queries `(x, y, t)` in Mo's algorithm with updates.
+///
+/// Orders by the blocks of `x` and `y`, then by `t`, alternating the
+/// direction of `y` and `t` so consecutive blocks meet close by.
+pub fn time_order(x: u32, y: u32, t: u32, block: u32) -> (u32, u32, u32) {
+    let xb = x / block;
+    let yb = y / block;
+    let yb = if xb & 1 == 0 { yb } else { !yb };
+    let t = if yb & 1 == 0 { t } else { !t };
+    (xb, yb, t)
+}
+
 /// Triangular space filling curve for sorting queries
 /// with small sum of Manhattan distances.
 ///
@@ -84,7 +251,7 @@ pub fn triangle_order(x: u32, y: u32, n: u32) -> u64 {
 
     let sml = (n - 1) / 2;
     let med = n / 2;
-    let big = (n + 1) / 2;
+    let big = n.div_ceil(2);
 
     let med_area = med as u64 * (med as u64 + 1) / 2;
     let big_area = big as u64 * (big as u64 + 1) / 2;
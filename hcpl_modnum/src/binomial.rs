@@ -0,0 +1,140 @@
+use super::Modnum;
+
+/// Lazily grown tables of factorials and inverse factorials modulo the
+/// prime `MOD`.
+///
+/// Only arguments below `MOD` are supported, except in [`Binomial::lucas`].
+///
+/// ```
+/// use hcpl_modnum::Binomial;
+///
+/// let mut binom = Binomial::<998244353>::new();
+/// assert_eq!(u32::from(binom.choose(5, 2)), 10);
+/// assert_eq!(u32::from(binom.perm(5, 2)), 20);
+/// assert_eq!(u32::from(binom.multinomial(&[1, 2, 3])), 60);
+/// assert_eq!(u32::from(binom.catalan(4)), 14);
+/// assert_eq!(binom.inv(3) * binom.fact(3), binom.fact(2));
+///
+/// // with a small modulus, everything below it is still supported
+/// let mut small = Binomial::<7>::new();
+/// assert_eq!(u32::from(small.catalan(3)), 5);
+/// assert_eq!(u32::from(small.choose(6, 3)), 20 % 7);
+/// assert_eq!(u32::from(small.lucas(10, 3)), 120 % 7);
+/// ```
+#[derive(Clone, Debug)]
+pub struct Binomial<const MOD: u32> {
+    fact: Vec<Modnum<MOD>>,
+    inv_fact: Vec<Modnum<MOD>>,
+}
+
+impl<const MOD: u32> Default for Binomial<MOD> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const MOD: u32> Binomial<MOD> {
+    /// Creates empty tables, which grow on demand.
+    pub fn new() -> Self {
+        Self {
+            fact: vec![Modnum::new(1)],
+            inv_fact: vec![Modnum::new(1)],
+        }
+    }
+
+    /// Creates tables covering `0..=n`.
+    pub fn with_size(n: usize) -> Self {
+        let mut res = Self::new();
+        res.grow(n);
+        res
+    }
+
+    /// Extends the tables to cover `0..=n`.
+    fn grow(&mut self, n: usize) {
+        let old_len = self.fact.len();
+        if n < old_len {
+            return;
+        }
+        assert!(n < MOD as usize);
+
+        let new_len = (n + 1).max(2 * old_len).min(MOD as usize);
+        for i in old_len..new_len {
+            let f = self.fact[i - 1] * Modnum::from(i);
+            self.fact.push(f);
+        }
+
+        self.inv_fact.resize(new_len, Modnum::new(0));
+        self.inv_fact[new_len - 1] = self.fact[new_len - 1].inv();
+        for i in (old_len..new_len - 1).rev() {
+            self.inv_fact[i] = self.inv_fact[i + 1] * Modnum::from(i + 1);
+        }
+    }
+
+    /// Returns `n!`.
+    pub fn fact(&mut self, n: usize) -> Modnum<MOD> {
+        self.grow(n);
+        self.fact[n]
+    }
+
+    /// Returns `1 / n!`.
+    pub fn inv_fact(&mut self, n: usize) -> Modnum<MOD> {
+        self.grow(n);
+        self.inv_fact[n]
+    }
+
+    /// Returns `1 / n` for `n != 0`.
+    pub fn inv(&mut self, n: usize) -> Modnum<MOD> {
+        debug_assert!(n != 0);
+        self.grow(n);
+        self.fact[n - 1] * self.inv_fact[n]
+    }
+
+    /// Returns the number of ways to choose `k` elements out of `n`.
+    pub fn choose(&mut self, n: usize, k: usize) -> Modnum<MOD> {
+        if k > n {
+            return Modnum::new(0);
+        }
+        self.grow(n);
+        self.fact[n] * self.inv_fact[k] * self.inv_fact[n - k]
+    }
+
+    /// Returns the number of ordered arrangements of `k` elements out of `n`.
+    pub fn perm(&mut self, n: usize, k: usize) -> Modnum<MOD> {
+        if k > n {
+            return Modnum::new(0);
+        }
+        self.grow(n);
+        self.fact[n] * self.inv_fact[n - k]
+    }
+
+    /// Returns the multinomial coefficient `(sum ks)! / prod (k!)`.
+    pub fn multinomial(&mut self, ks: &[usize]) -> Modnum<MOD> {
+        let n = ks.iter().sum();
+        let mut res = self.fact(n);
+        for &k in ks {
+            res *= self.inv_fact[k];
+        }
+        res
+    }
+
+    /// Returns the `n`th Catalan number.
+    pub fn catalan(&mut self, n: usize) -> Modnum<MOD> {
+        self.grow((2 * n).max(n + 1));
+        self.fact[2 * n] * self.inv_fact[n] * self.inv_fact[n + 1]
+    }
+
+    /// Returns the number of ways to choose `k` elements out of `n` using
+    /// Lucas's theorem, for arbitrarily large `n`.
+    ///
+    /// Builds tables of size `MOD`, so `MOD` should be small.
+    pub fn lucas(&mut self, mut n: u64, mut k: u64) -> Modnum<MOD> {
+        let m = MOD as u64;
+        let mut res = Modnum::new(1);
+        while k != 0 {
+            res *= self.choose((n % m) as usize, (k % m) as usize);
+            n /= m;
+            k /= m;
+        }
+        res
+    }
+}
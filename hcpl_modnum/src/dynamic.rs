@@ -0,0 +1,237 @@
+use super::inv_gcd;
+use std::{
+    cell::Cell,
+    fmt::Display,
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+/// Barrett reduction for moduli that fit in a `u32`.
+#[derive(Copy, Clone, Debug)]
+struct Barrett {
+    m: u32,
+    // ceil(2^64 / m), wrapping to 0 when m = 1
+    im: u64,
+}
+
+impl Barrett {
+    const fn new(m: u32) -> Self {
+        Self {
+            m,
+            im: (u64::MAX / m as u64).wrapping_add(1),
+        }
+    }
+
+    /// Computes `a * b % m` for `a, b < m`.
+    fn mul(&self, a: u32, b: u32) -> u32 {
+        let z = a as u64 * b as u64;
+        // x is either z / m or z / m + 1
+        let x = ((z as u128 * self.im as u128) >> 64) as u64;
+        let (v, borrow) = z.overflowing_sub(x * self.m as u64);
+        if borrow {
+            v.wrapping_add(self.m as u64) as u32
+        } else {
+            v as u32
+        }
+    }
+}
+
+thread_local! {
+    static BARRETT: Cell<Barrett> = const { Cell::new(Barrett::new(998_244_353)) };
+}
+
+fn barrett() -> Barrett {
+    BARRETT.with(Cell::get)
+}
+
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+/// An element of ℤ/mℤ where m is chosen at runtime with
+/// [`DynModnum::set_modulus`]. Implemented over `u32`.
+///
+/// The modulus is thread-local and defaults to 998244353. Changing it
+/// invalidates all existing `DynModnum`s of the current thread.
+///
+/// ```
+/// use hcpl_modnum::DynModnum;
+///
+/// assert_eq!(DynModnum::modulus(), 998244353);
+/// assert_eq!(DynModnum::new(998244352) + DynModnum::new(2), DynModnum::new(1));
+///
+/// DynModnum::set_modulus(1_000_000_007);
+/// let x = DynModnum::new(500_000_004);
+/// assert_eq!(u32::from(x * DynModnum::new(2)), 1);
+/// assert_eq!(DynModnum::new(3).inv() * DynModnum::new(3), DynModnum::new(1));
+///
+/// // other threads keep the default modulus
+/// std::thread::spawn(|| assert_eq!(DynModnum::modulus(), 998244353))
+///     .join()
+///     .unwrap();
+/// ```
+pub struct DynModnum(u32);
+
+impl Display for DynModnum {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        <u32 as Display>::fmt(&self.0, f)
+    }
+}
+
+impl DynModnum {
+    /// Sets the modulus used by all `DynModnum`s of the current thread.
+    pub fn set_modulus(m: u32) {
+        assert!(m != 0);
+        BARRETT.with(|b| b.set(Barrett::new(m)));
+    }
+
+    /// Returns the modulus used by `DynModnum`s of the current thread.
+    pub fn modulus() -> u32 {
+        barrett().m
+    }
+
+    /// Constructs a `DynModnum` from a `u32`. Equivalent to `DynModnum::from(x)`
+    pub fn new(x: u32) -> Self {
+        Self(x % Self::modulus())
+    }
+
+    /// Modular exponentiation by squaring.
+    pub fn pow(mut self, mut e: usize) -> Self {
+        let mut a = Self::new(1);
+        while e != 0 {
+            if e & 1 == 1 {
+                a *= self;
+            }
+            self *= self;
+
+            e >>= 1;
+        }
+        a
+    }
+
+    /// Calculates the inverse of `self`, **assuming the modulus is prime**.
+    ///
+    /// See [`DynModnum::checked_inv`] for arbitrary moduli.
+    pub fn inv(self) -> Self {
+        self.pow(Self::modulus() as usize - 2)
+    }
+
+    /// Calculates the inverse of `self` for any modulus, or returns `None`
+    /// if `self` is not coprime to the modulus.
+    pub fn checked_inv(self) -> Option<Self> {
+        inv_gcd(self.0 as u64, Self::modulus() as u64).map(|x| Self(x as u32))
+    }
+}
+
+impl From<u32> for DynModnum {
+    fn from(n: u32) -> Self {
+        Self::new(n)
+    }
+}
+
+impl From<usize> for DynModnum {
+    fn from(x: usize) -> Self {
+        Self((x % Self::modulus() as usize) as u32)
+    }
+}
+
+impl From<DynModnum> for u32 {
+    fn from(x: DynModnum) -> u32 {
+        x.0
+    }
+}
+
+impl Add for DynModnum {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let m = Self::modulus();
+        let (res, overflow) = self.0.overflowing_add(rhs.0);
+        Self(if overflow || res >= m {
+            res.wrapping_sub(m)
+        } else {
+            res
+        })
+    }
+}
+
+impl Neg for DynModnum {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        if self.0 == 0 {
+            self
+        } else {
+            Self(Self::modulus() - self.0)
+        }
+    }
+}
+
+impl Sub for DynModnum {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        self + (-rhs)
+    }
+}
+
+impl Mul for DynModnum {
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Self(barrett().mul(self.0, rhs.0))
+    }
+}
+
+impl Div for DynModnum {
+    type Output = Self;
+
+    /// Panics in debug builds if `rhs` is not invertible.
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, rhs: Self) -> Self::Output {
+        let inv = rhs.checked_inv();
+        debug_assert!(
+            inv.is_some(),
+            "{} is not invertible modulo {}",
+            rhs,
+            Self::modulus()
+        );
+        self * inv.unwrap_or(Self(0))
+    }
+}
+
+impl AddAssign for DynModnum {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl SubAssign for DynModnum {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+impl MulAssign for DynModnum {
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+impl DivAssign for DynModnum {
+    fn div_assign(&mut self, rhs: Self) {
+        *self = *self / rhs;
+    }
+}
+
+impl hcpl_algebra::monoid::AdditiveIdentity for DynModnum {
+    const VALUE: Self = Self(0);
+}
+
+impl hcpl_algebra::monoid::MultiplicativeIdentity for DynModnum {
+    /// **Assumes the modulus is not 1.**
+    const VALUE: Self = Self(1);
+}
+
+impl hcpl_algebra::group::MultiplicativeInverse for DynModnum {
+    /// Calculates the inverse of `self`, **assuming the modulus is prime**.
+    fn mul_inv(self) -> Self {
+        self.inv()
+    }
+}
@@ -1,3 +1,11 @@
+mod binomial;
+mod dynamic;
+mod montgomery;
+
+pub use binomial::Binomial;
+pub use dynamic::DynModnum;
+pub use montgomery::{DynModnum64, Modnum64, Montgomery64};
+
 use std::{
     fmt::Display,
     ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
@@ -35,9 +43,45 @@ impl<const MOD: u32> Modnum<MOD> {
     }
 
     /// Calculates the inverse of `self`, **assuming `MOD` is prime**.
+    ///
+    /// See [`Modnum::checked_inv`] for arbitrary moduli.
     pub fn inv(self) -> Self {
         self.pow(MOD as usize - 2)
     }
+
+    /// Calculates the inverse of `self` for any `MOD`, or returns `None`
+    /// if `self` is not coprime to `MOD`.
+    pub fn checked_inv(self) -> Option<Self> {
+        inv_gcd(self.0 as u64, MOD as u64).map(|x| Self(x as u32))
+    }
+}
+
+/// Computes the inverse of `a` modulo `m` with the extended Euclidean
+/// algorithm, if `gcd(a, m) = 1`.
+fn inv_gcd(a: u64, m: u64) -> Option<u64> {
+    macro_rules! inv_gcd_as {
+        ($t:ty) => {{
+            let (mut a, mut b) = (a as $t, m as $t);
+            let (mut x, mut y): ($t, $t) = (1, 0);
+            while b != 0 {
+                let q = a / b;
+                (a, b) = (b, a - q * b);
+                (x, y) = (y, x - q * y);
+            }
+            if a == 1 {
+                Some(x.rem_euclid(m as $t) as u64)
+            } else {
+                None
+            }
+        }};
+    }
+
+    // all intermediate values are bounded by m in absolute value
+    if m <= i64::MAX as u64 {
+        inv_gcd_as!(i64)
+    } else {
+        inv_gcd_as!(i128)
+    }
 }
 
 impl<const MOD: u32> From<u32> for Modnum<MOD> {
@@ -98,9 +142,12 @@ impl<const MOD: u32> Mul for Modnum<MOD> {
 impl<const MOD: u32> Div for Modnum<MOD> {
     type Output = Self;
 
+    /// Panics in debug builds if `rhs` is not invertible.
     #[allow(clippy::suspicious_arithmetic_impl)]
     fn div(self, rhs: Self) -> Self::Output {
-        self * rhs.inv()
+        let inv = rhs.checked_inv();
+        debug_assert!(inv.is_some(), "{} is not invertible modulo {}", rhs, MOD);
+        self * inv.unwrap_or(Self(0))
     }
 }
 
@@ -135,3 +182,10 @@ impl<const MOD: u32> hcpl_algebra::monoid::AdditiveIdentity for Modnum<MOD> {
 impl<const MOD: u32> hcpl_algebra::monoid::MultiplicativeIdentity for Modnum<MOD> {
     const VALUE: Self = Self::new(1);
 }
+
+impl<const MOD: u32> hcpl_algebra::group::MultiplicativeInverse for Modnum<MOD> {
+    /// Calculates the inverse of `self`, **assuming `MOD` is prime**.
+    fn mul_inv(self) -> Self {
+        self.inv()
+    }
+}
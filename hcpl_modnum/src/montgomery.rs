@@ -0,0 +1,350 @@
+use super::inv_gcd;
+use std::{
+    cell::Cell,
+    fmt::{Debug, Display},
+    ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Sub, SubAssign},
+};
+
+/// Montgomery multiplication modulo an odd `u64`, with R = 2^64.
+///
+/// Values passed to and returned from the arithmetic methods are in
+/// Montgomery form, ie. `x` is represented by `x * R % n`.
+#[derive(Copy, Clone, Debug)]
+pub struct Montgomery64 {
+    n: u64,
+    // n * n_inv = 1 (mod R)
+    n_inv: u64,
+    // R^2 % n
+    r2: u64,
+}
+
+impl Montgomery64 {
+    /// Precomputes the constants for Montgomery multiplication modulo `n`.
+    /// **`n` must be odd.**
+    pub const fn new(n: u64) -> Self {
+        assert!(n & 1 == 1);
+
+        // Newton's iteration, each step doubles the number of correct bits
+        let mut n_inv = n;
+        let mut i = 0;
+        while i < 5 {
+            n_inv = n_inv.wrapping_mul(2u64.wrapping_sub(n.wrapping_mul(n_inv)));
+            i += 1;
+        }
+
+        let r = ((u64::MAX % n) as u128 + 1) % n as u128;
+        let r2 = (r * r % n as u128) as u64;
+
+        Self { n, n_inv, r2 }
+    }
+
+    /// Returns the modulus.
+    pub const fn modulus(&self) -> u64 {
+        self.n
+    }
+
+    /// Computes `t / R % n` for `t < n * R`.
+    pub const fn reduce(&self, t: u128) -> u64 {
+        let m = (t as u64).wrapping_mul(self.n_inv);
+        let mn = ((m as u128 * self.n as u128) >> 64) as u64;
+        let (res, borrow) = ((t >> 64) as u64).overflowing_sub(mn);
+        if borrow {
+            res.wrapping_add(self.n)
+        } else {
+            res
+        }
+    }
+
+    /// Converts `x` into Montgomery form.
+    pub const fn to_mont(&self, x: u64) -> u64 {
+        self.mul(x % self.n, self.r2)
+    }
+
+    /// Converts `x` out of Montgomery form.
+    pub const fn from_mont(&self, x: u64) -> u64 {
+        self.reduce(x as u128)
+    }
+
+    /// Modular multiplication of two values in Montgomery form.
+    pub const fn mul(&self, a: u64, b: u64) -> u64 {
+        self.reduce(a as u128 * b as u128)
+    }
+
+    /// Modular addition of two values in `0..n`.
+    pub const fn add(&self, a: u64, b: u64) -> u64 {
+        let (res, overflow) = a.overflowing_add(b);
+        if overflow || res >= self.n {
+            res.wrapping_sub(self.n)
+        } else {
+            res
+        }
+    }
+
+    /// Modular subtraction of two values in `0..n`.
+    pub const fn sub(&self, a: u64, b: u64) -> u64 {
+        let (res, borrow) = a.overflowing_sub(b);
+        if borrow {
+            res.wrapping_add(self.n)
+        } else {
+            res
+        }
+    }
+
+    /// Modular exponentiation by squaring of a value in Montgomery form.
+    pub fn pow(&self, mut b: u64, mut e: u64) -> u64 {
+        let mut a = self.to_mont(1);
+        while e != 0 {
+            if e & 1 == 1 {
+                a = self.mul(a, b);
+            }
+            b = self.mul(b, b);
+            e >>= 1;
+        }
+        a
+    }
+}
+
+macro_rules! impl_montgomery_modnum {
+    ([$($gen:tt)*] $name:ident, $t:ty, $mont:expr) => {
+        impl<$($gen)*> $t {
+            /// Modular exponentiation by squaring.
+            pub fn pow(self, e: usize) -> Self {
+                Self($mont.pow(self.0, e as u64))
+            }
+
+            /// Calculates the inverse of `self`, **assuming the modulus is prime**.
+            ///
+            #[doc = concat!("See [`", stringify!($name), "::checked_inv`] for arbitrary moduli.")]
+            pub fn inv(self) -> Self {
+                self.pow($mont.modulus() as usize - 2)
+            }
+
+            /// Calculates the inverse of `self` for any modulus, or returns `None`
+            /// if `self` is not coprime to the modulus.
+            pub fn checked_inv(self) -> Option<Self> {
+                let mont = $mont;
+                inv_gcd(mont.from_mont(self.0), mont.modulus()).map(|x| Self(mont.to_mont(x)))
+            }
+        }
+
+        impl<$($gen)*> Display for $t {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                <u64 as Display>::fmt(&$mont.from_mont(self.0), f)
+            }
+        }
+
+        impl<$($gen)*> Debug for $t {
+            fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+                f.debug_tuple(stringify!($name))
+                    .field(&$mont.from_mont(self.0))
+                    .finish()
+            }
+        }
+
+        impl<$($gen)*> From<u64> for $t {
+            fn from(x: u64) -> Self {
+                Self($mont.to_mont(x))
+            }
+        }
+
+        impl<$($gen)*> From<usize> for $t {
+            fn from(x: usize) -> Self {
+                Self($mont.to_mont(x as u64))
+            }
+        }
+
+        impl<$($gen)*> From<$t> for u64 {
+            fn from(x: $t) -> u64 {
+                $mont.from_mont(x.0)
+            }
+        }
+
+        impl<$($gen)*> Add for $t {
+            type Output = Self;
+
+            fn add(self, rhs: Self) -> Self::Output {
+                Self($mont.add(self.0, rhs.0))
+            }
+        }
+
+        impl<$($gen)*> Neg for $t {
+            type Output = Self;
+
+            fn neg(self) -> Self::Output {
+                Self($mont.sub(0, self.0))
+            }
+        }
+
+        impl<$($gen)*> Sub for $t {
+            type Output = Self;
+
+            fn sub(self, rhs: Self) -> Self::Output {
+                Self($mont.sub(self.0, rhs.0))
+            }
+        }
+
+        impl<$($gen)*> Mul for $t {
+            type Output = Self;
+
+            fn mul(self, rhs: Self) -> Self::Output {
+                Self($mont.mul(self.0, rhs.0))
+            }
+        }
+
+        impl<$($gen)*> Div for $t {
+            type Output = Self;
+
+            /// Panics in debug builds if `rhs` is not invertible.
+            #[allow(clippy::suspicious_arithmetic_impl)]
+            fn div(self, rhs: Self) -> Self::Output {
+                let inv = rhs.checked_inv();
+                debug_assert!(inv.is_some(), "{} is not invertible modulo {}", rhs, $mont.modulus());
+                self * inv.unwrap_or(Self(0))
+            }
+        }
+
+        impl<$($gen)*> AddAssign for $t {
+            fn add_assign(&mut self, rhs: Self) {
+                *self = *self + rhs;
+            }
+        }
+
+        impl<$($gen)*> SubAssign for $t {
+            fn sub_assign(&mut self, rhs: Self) {
+                *self = *self - rhs;
+            }
+        }
+
+        impl<$($gen)*> MulAssign for $t {
+            fn mul_assign(&mut self, rhs: Self) {
+                *self = *self * rhs;
+            }
+        }
+
+        impl<$($gen)*> DivAssign for $t {
+            fn div_assign(&mut self, rhs: Self) {
+                *self = *self / rhs;
+            }
+        }
+
+        impl<$($gen)*> hcpl_algebra::monoid::AdditiveIdentity for $t {
+            const VALUE: Self = Self(0);
+        }
+
+        impl<$($gen)*> hcpl_algebra::group::MultiplicativeInverse for $t {
+            /// Calculates the inverse of `self`, **assuming the modulus is prime**.
+            fn mul_inv(self) -> Self {
+                self.inv()
+            }
+        }
+    };
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+/// An element of ℤ/mℤ where m = MOD. Implemented over `u64` in Montgomery form.
+///
+/// **`MOD` must be odd.**
+///
+/// Any odd modulus below 2^64 works, including the ones close to `u64::MAX`:
+///
+/// ```
+/// use hcpl_modnum::Modnum64;
+///
+/// fn check<const MOD: u64>() {
+///     let mut x: u64 = 0x9e37_79b9_7f4a_7c15;
+///     for _ in 0..1000 {
+///         x = x.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+///         let (a, b) = (x % MOD, x.rotate_left(29) % MOD);
+///         let (ma, mb) = (Modnum64::<MOD>::new(a), Modnum64::<MOD>::new(b));
+///
+///         let product = (a as u128 * b as u128 % MOD as u128) as u64;
+///         let sum = ((a as u128 + b as u128) % MOD as u128) as u64;
+///         assert_eq!(u64::from(ma * mb), product);
+///         assert_eq!(u64::from(ma + mb), sum);
+///         assert_eq!(ma + mb - mb, ma);
+///     }
+///     assert_eq!(u64::from(Modnum64::<MOD>::new(u64::MAX)), u64::MAX % MOD);
+///     assert_eq!(u64::from(-Modnum64::<MOD>::new(1)), MOD - 1);
+/// }
+///
+/// check::<998_244_353>();
+/// check::<{ (1 << 61) - 1 }>();
+/// check::<18_446_744_073_709_551_557>(); // the largest prime below 2^64
+/// check::<{ u64::MAX }>();
+///
+/// type M = Modnum64<{ (1 << 61) - 1 }>;
+/// assert_eq!(M::new(3).inv() * M::new(3), M::new(1));
+/// assert_eq!(M::new(2).pow(61), M::new(1));
+/// ```
+pub struct Modnum64<const MOD: u64>(u64);
+
+impl<const MOD: u64> Modnum64<MOD> {
+    const MONT: Montgomery64 = Montgomery64::new(MOD);
+
+    /// Constructs a `Modnum64<MOD>` from a `u64`. Equivalent to `Modnum64::from(x)`
+    pub const fn new(x: u64) -> Self {
+        Self(Self::MONT.to_mont(x))
+    }
+}
+
+impl_montgomery_modnum!([const MOD: u64] Modnum64, Modnum64<MOD>, Modnum64::<MOD>::MONT);
+
+impl<const MOD: u64> hcpl_algebra::monoid::MultiplicativeIdentity for Modnum64<MOD> {
+    const VALUE: Self = Self::new(1);
+}
+
+thread_local! {
+    static MONTGOMERY: Cell<Montgomery64> = const { Cell::new(Montgomery64::new(998_244_353)) };
+}
+
+fn montgomery() -> Montgomery64 {
+    MONTGOMERY.with(Cell::get)
+}
+
+#[derive(Copy, Clone, Eq, PartialEq)]
+/// An element of ℤ/mℤ where m is chosen at runtime with
+/// [`DynModnum64::set_modulus`]. Implemented over `u64` in Montgomery form.
+///
+/// The modulus is thread-local, must be odd, and defaults to 998244353.
+/// Changing it invalidates all existing `DynModnum64`s of the current thread.
+///
+/// Unlike [`Modnum64`], this does not implement `MultiplicativeIdentity`,
+/// because the Montgomery form of 1 depends on the modulus.
+///
+/// ```
+/// use hcpl_modnum::DynModnum64;
+///
+/// assert_eq!(DynModnum64::modulus(), 998244353);
+///
+/// // the largest prime below 2^64
+/// let p = 18_446_744_073_709_551_557;
+/// DynModnum64::set_modulus(p);
+/// let x = DynModnum64::new(p - 1);
+/// assert_eq!(x * x, DynModnum64::new(1));
+/// assert_eq!(u64::from(x + DynModnum64::new(5)), 4);
+/// assert_eq!(DynModnum64::new(7).inv() * DynModnum64::new(7), DynModnum64::new(1));
+///
+/// DynModnum64::set_modulus((1 << 61) - 1);
+/// assert_eq!(DynModnum64::new(2).pow(61), DynModnum64::new(1));
+/// ```
+pub struct DynModnum64(u64);
+
+impl DynModnum64 {
+    /// Sets the modulus used by all `DynModnum64`s of the current thread.
+    /// **`m` must be odd.**
+    pub fn set_modulus(m: u64) {
+        MONTGOMERY.with(|mont| mont.set(Montgomery64::new(m)));
+    }
+
+    /// Returns the modulus used by `DynModnum64`s of the current thread.
+    pub fn modulus() -> u64 {
+        montgomery().modulus()
+    }
+
+    /// Constructs a `DynModnum64` from a `u64`. Equivalent to `DynModnum64::from(x)`
+    pub fn new(x: u64) -> Self {
+        Self(montgomery().to_mont(x))
+    }
+}
+
+impl_montgomery_modnum!([] DynModnum64, DynModnum64, montgomery());
@@ -0,0 +1,251 @@
+use hcpl_modnum::Modnum;
+
+const fn pow_mod(mut b: u64, mut e: u64, m: u64) -> u64 {
+    let mut a = 1;
+    while e != 0 {
+        if e & 1 == 1 {
+            a = a * b % m;
+        }
+        b = b * b % m;
+        e >>= 1;
+    }
+    a
+}
+
+/// Finds the smallest primitive root modulo the prime `m`.
+const fn primitive_root(m: u32) -> u32 {
+    if m == 2 {
+        return 1;
+    }
+
+    let m = m as u64;
+    let mut divs = [0u64; 32];
+    let mut cnt = 0;
+
+    let mut x = m - 1;
+    let mut i = 2;
+    while i * i <= x {
+        if x.is_multiple_of(i) {
+            divs[cnt] = i;
+            cnt += 1;
+            while x.is_multiple_of(i) {
+                x /= i;
+            }
+        }
+        i += 1;
+    }
+    if x > 1 {
+        divs[cnt] = x;
+        cnt += 1;
+    }
+
+    let mut g = 2;
+    loop {
+        let mut k = 0;
+        while k < cnt && pow_mod(g, (m - 1) / divs[k], m) != 1 {
+            k += 1;
+        }
+        if k == cnt {
+            return g as u32;
+        }
+        g += 1;
+    }
+}
+
+struct NttInfo<const MOD: u32>;
+
+impl<const MOD: u32> NttInfo<MOD> {
+    const PRIMITIVE_ROOT: u32 = primitive_root(MOD);
+    const RANK2: u32 = (MOD - 1).trailing_zeros();
+}
+
+/// In-place number-theoretic transform of slice `a`.
+///
+/// `MOD` must be a prime and `a.len()` must be a power of two dividing `MOD - 1`.
+/// The inverse transform (`inv = true`) includes the division by `a.len()`.
+///
+/// ```
+/// use hcpl_modnum::Modnum;
+/// use hcpl_ntt::ntt;
+///
+/// let a: Vec<Modnum<998244353>> = [1u32, 2, 3, 4].map(Modnum::new).to_vec();
+/// let mut b = a.clone();
+/// ntt(&mut b, false);
+/// assert_eq!(b[0], Modnum::new(10)); // the transform at x = 1 is the sum
+/// ntt(&mut b, true);
+/// assert_eq!(b, a);
+/// ```
+pub fn ntt<const MOD: u32>(a: &mut [Modnum<MOD>], inv: bool) {
+    let n = a.len();
+    assert!(n.is_power_of_two());
+    assert!(n.trailing_zeros() <= NttInfo::<MOD>::RANK2);
+
+    let mut j = 0;
+    for i in 1..n {
+        let mut bit = n >> 1;
+        while j & bit != 0 {
+            j ^= bit;
+            bit >>= 1;
+        }
+        j ^= bit;
+        if i < j {
+            a.swap(i, j);
+        }
+    }
+
+    let g = Modnum::<MOD>::new(NttInfo::<MOD>::PRIMITIVE_ROOT);
+    let g = if inv { g.inv() } else { g };
+
+    let mut roots = Vec::with_capacity(n / 2);
+    let mut len = 2;
+    while len <= n {
+        let w = g.pow((MOD - 1) as usize / len);
+        roots.clear();
+        roots.push(Modnum::new(1));
+        for k in 1..len / 2 {
+            roots.push(roots[k - 1] * w);
+        }
+
+        for chunk in a.chunks_exact_mut(len) {
+            let (l, r) = chunk.split_at_mut(len / 2);
+            for ((x, y), &w) in l.iter_mut().zip(r).zip(&roots) {
+                let u = *x;
+                let v = *y * w;
+                *x = u + v;
+                *y = u - v;
+            }
+        }
+        len <<= 1;
+    }
+
+    if inv {
+        let n_inv = Modnum::<MOD>::from(n).inv();
+        for x in a.iter_mut() {
+            *x *= n_inv;
+        }
+    }
+}
+
+fn convolution_naive<const MOD: u32>(a: &[Modnum<MOD>], b: &[Modnum<MOD>]) -> Vec<Modnum<MOD>> {
+    let mut res = vec![Modnum::new(0); a.len() + b.len() - 1];
+    for (i, &x) in a.iter().enumerate() {
+        for (j, &y) in b.iter().enumerate() {
+            res[i + j] += x * y;
+        }
+    }
+    res
+}
+
+/// Computes the product of the polynomials with coefficients `a` and `b`.
+///
+/// `MOD` must be a prime such that `MOD - 1` is divisible by a power of two
+/// at least `a.len() + b.len() - 1`, eg. 998244353.
+///
+/// ```
+/// use hcpl_modnum::Modnum;
+/// use hcpl_ntt::convolution;
+///
+/// type Mint = Modnum<998244353>;
+/// let a: Vec<Mint> = (0..100u32).map(|i| Mint::new(i * i + 1)).collect();
+/// let b: Vec<Mint> = (0..80u32).map(|i| Mint::new(7 * i + 3)).collect();
+///
+/// let mut naive = vec![Mint::new(0); a.len() + b.len() - 1];
+/// for (i, &x) in a.iter().enumerate() {
+///     for (j, &y) in b.iter().enumerate() {
+///         naive[i + j] += x * y;
+///     }
+/// }
+/// assert_eq!(convolution(&a, &b), naive);
+/// ```
+pub fn convolution<const MOD: u32>(a: &[Modnum<MOD>], b: &[Modnum<MOD>]) -> Vec<Modnum<MOD>> {
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+    if a.len().min(b.len()) <= 60 {
+        return convolution_naive(a, b);
+    }
+
+    let n = a.len() + b.len() - 1;
+    let size = n.next_power_of_two();
+
+    let mut fa = a.to_vec();
+    let mut fb = b.to_vec();
+    fa.resize(size, Modnum::new(0));
+    fb.resize(size, Modnum::new(0));
+    ntt(&mut fa, false);
+    ntt(&mut fb, false);
+    for (x, y) in fa.iter_mut().zip(fb) {
+        *x *= y;
+    }
+    ntt(&mut fa, true);
+    fa.truncate(n);
+    fa
+}
+
+fn convolution_as<const FROM: u32, const TO: u32>(
+    a: &[Modnum<FROM>],
+    b: &[Modnum<FROM>],
+) -> Vec<Modnum<TO>> {
+    let cast = |v: &[Modnum<FROM>]| -> Vec<Modnum<TO>> {
+        v.iter().map(|&x| Modnum::new(u32::from(x))).collect()
+    };
+    convolution(&cast(a), &cast(b))
+}
+
+/// Computes the product of the polynomials with coefficients `a` and `b`
+/// for any `MOD`, using three NTT-friendly primes and Garner's algorithm.
+///
+/// The length of the result must be at most 2^24, and
+/// `min(a.len(), b.len()) * (MOD - 1)^2` must be less than the product
+/// of the three primes, roughly 5.9 * 10^25.
+///
+/// ```
+/// use hcpl_modnum::Modnum;
+/// use hcpl_ntt::convolution_any_mod;
+///
+/// type Mint = Modnum<1_000_000_007>;
+/// let a: Vec<Mint> = (0..100u32).map(|i| Mint::new(1_000_000_006 - i)).collect();
+/// let b: Vec<Mint> = (0..80u32).map(|i| Mint::new(999_999_937 + i)).collect();
+///
+/// let mut naive = vec![Mint::new(0); a.len() + b.len() - 1];
+/// for (i, &x) in a.iter().enumerate() {
+///     for (j, &y) in b.iter().enumerate() {
+///         naive[i + j] += x * y;
+///     }
+/// }
+/// assert_eq!(convolution_any_mod(&a, &b), naive);
+/// ```
+pub fn convolution_any_mod<const MOD: u32>(
+    a: &[Modnum<MOD>],
+    b: &[Modnum<MOD>],
+) -> Vec<Modnum<MOD>> {
+    const M1: u32 = 754_974_721;
+    const M2: u32 = 167_772_161;
+    const M3: u32 = 469_762_049;
+
+    if a.is_empty() || b.is_empty() {
+        return Vec::new();
+    }
+
+    let c1 = convolution_as::<MOD, M1>(a, b);
+    let c2 = convolution_as::<MOD, M2>(a, b);
+    let c3 = convolution_as::<MOD, M3>(a, b);
+
+    let m1_inv_m2 = Modnum::<M2>::new(M1).inv();
+    let m1m2_inv_m3 = (Modnum::<M3>::new(M1) * Modnum::new(M2)).inv();
+    let m1_mod = M1 as u64 % MOD as u64;
+    let m1m2_mod = M1 as u64 * M2 as u64 % MOD as u64;
+
+    c1.into_iter()
+        .zip(c2)
+        .zip(c3)
+        .map(|((r1, r2), r3)| {
+            let r1 = u32::from(r1);
+            let t1 = u32::from((r2 - Modnum::new(r1)) * m1_inv_m2);
+            let x12 = r1 as u64 + M1 as u64 * t1 as u64;
+            let t2 = u32::from((r3 - Modnum::new((x12 % M3 as u64) as u32)) * m1m2_inv_m3);
+            let res = (r1 as u64 + m1_mod * t1 as u64 + m1m2_mod * t2 as u64) % MOD as u64;
+            Modnum::new(res as u32)
+        })
+        .collect()
+}
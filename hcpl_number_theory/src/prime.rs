@@ -1,40 +1,29 @@
 // Ported from https://github.com/kth-competitive-programming/kactl/blob/main/content/number-theory/Factor.h
 
 use super::gcd;
-
-fn modmul(x: u64, y: u64, m: u64) -> u64 {
-    (x as u128 * y as u128 % m as u128) as u64
-}
-
-fn modpow(mut b: u64, mut e: u64, m: u64) -> u64 {
-    let mut a = 1;
-    while e != 0 {
-        if e & 1 == 1 {
-            a = modmul(a, b, m);
-        }
-        b = modmul(b, b, m);
-        e >>= 1;
-    }
-    a
-}
+use hcpl_modnum::Montgomery64;
 
 pub fn is_prime(n: u64) -> bool {
     if n < 2 || n % 6 % 4 != 1 {
         return (n | 1) == 3;
     }
 
+    let mont = Montgomery64::new(n);
+    let one = mont.to_mont(1);
+    let minus_one = mont.to_mont(n - 1);
+
     let a = [2, 325, 9375, 28178, 450775, 9780504, 1795265022];
     let s = (n - 1).trailing_zeros();
     let d = n >> s;
 
     for x in a {
-        let mut p = modpow(x % n, d, n);
+        let mut p = mont.pow(mont.to_mont(x), d);
         let mut i = s;
-        while p != 1 && p != n - 1 && x % n != 0 && i != 0 {
+        while p != one && p != minus_one && x % n != 0 && i != 0 {
             i -= 1;
-            p = modmul(p, p, n);
+            p = mont.mul(p, p);
         }
-        if p != n - 1 && i != s {
+        if p != minus_one && i != s {
             return false;
         }
     }
@@ -43,22 +32,30 @@ pub fn is_prime(n: u64) -> bool {
 }
 
 pub fn pollard(n: u64) -> u64 {
-    let f = |x: u64| modmul(x, x, n) + 1;
+    if n & 1 == 0 {
+        return 2;
+    }
+
+    // All values below are in Montgomery form, which doesn't affect the gcds
+    // because R is coprime to n.
+    let mont = Montgomery64::new(n);
+    let one = mont.to_mont(1);
+    let f = |x: u64| mont.add(mont.mul(x, x), one);
     let mut x = 0;
     let mut y = 0;
     let mut t = 30;
-    let mut prd = 2;
+    let mut prd = one;
     let mut i = 1;
 
     while t % 40 != 0 || gcd(prd, n) == 1 {
         t += 1;
         if x == y {
             i += 1;
-            x = i;
+            x = mont.to_mont(i);
             y = f(x);
         }
 
-        let q = modmul(prd, std::cmp::max(x, y) - std::cmp::min(x, y), n);
+        let q = mont.mul(prd, mont.sub(x, y));
         if q != 0 {
             prd = q;
         }
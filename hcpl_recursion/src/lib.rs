@@ -75,7 +75,4 @@ macro_rules! _recursion__let_rec_mut {
     }
 }
 
-pub use crate::{
-    _recursion__let_rec as let_rec,
-    _recursion__let_rec_mut as let_rec_mut,
-};
+pub use crate::{_recursion__let_rec as let_rec, _recursion__let_rec_mut as let_rec_mut};
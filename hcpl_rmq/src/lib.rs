@@ -1,28 +1,61 @@
-use std::{fmt::Debug, ops::RangeBounds};
+mod sparse_table;
+pub use sparse_table::SparseTable;
+
+use std::ops::RangeBounds;
 
 const INNER_SIZE: usize = 32;
 type Snapshot = u32;
 
+/// A strict total order used by [`RMQ`].
+pub trait Order<T> {
+    /// Returns `true` if `a` comes strictly before `b`.
+    fn less(&self, a: &T, b: &T) -> bool;
+}
+
+/// The order given by [`Ord`], making [`RMQ`] answer range minimum queries.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct NaturalOrder;
+
+/// The reverse of the order given by [`Ord`], making [`RMQ`] answer range maximum queries.
+#[derive(Copy, Clone, Debug, Default)]
+pub struct ReverseOrder;
+
+impl<T: Ord> Order<T> for NaturalOrder {
+    fn less(&self, a: &T, b: &T) -> bool {
+        a < b
+    }
+}
+
+impl<T: Ord> Order<T> for ReverseOrder {
+    fn less(&self, a: &T, b: &T) -> bool {
+        a > b
+    }
+}
+
+impl<T, F: Fn(&T, &T) -> bool> Order<T> for F {
+    fn less(&self, a: &T, b: &T) -> bool {
+        self(a, b)
+    }
+}
+
 #[derive(Copy, Clone)]
-struct Inner<T> {
+struct Inner {
     snapshots: [Snapshot; INNER_SIZE],
-    _marker: std::marker::PhantomData<T>,
 }
 
-impl<T: Ord + Clone + Debug> Inner<T> {
+impl Inner {
     fn new() -> Self {
         Self {
             snapshots: [0; INNER_SIZE],
-            _marker: std::marker::PhantomData,
         }
     }
 
-    fn init(&mut self, data: &[T]) -> u32 {
+    fn init<T, O: Order<T>>(&mut self, data: &[T], order: &O) -> u32 {
         let mut stack: Snapshot = 0;
         for i in 0..INNER_SIZE.min(data.len()) {
             while stack != 0 {
                 let last = std::mem::size_of::<Snapshot>() * 8 - stack.leading_zeros() as usize - 1;
-                if data[i] > data[last] {
+                if order.less(&data[last], &data[i]) {
                     break;
                 }
                 stack ^= 1 << last;
@@ -40,36 +73,42 @@ impl<T: Ord + Clone + Debug> Inner<T> {
     }
 }
 
-pub struct RMQ<'a, T> {
+/// The O(n) index shared by [`RMQ`] and [`OwnedRMQ`], storing positions only.
+struct Index {
     table: Vec<Vec<u32>>,
-    inners: Vec<Inner<T>>,
-    data: &'a [T],
+    inners: Vec<Inner>,
 }
 
-impl<'a, T: Ord + Clone + Debug> RMQ<'a, T> {
-    pub fn new(data: &'a [T]) -> Self {
+impl Index {
+    fn new<T, O: Order<T>>(data: &[T], order: &O) -> Self {
         let n = data.len();
-        let blocks = (n + INNER_SIZE - 1) / INNER_SIZE;
-        let lvls = std::mem::size_of::<usize>() * 8 - blocks.leading_zeros() as usize;
+        let blocks = n.div_ceil(INNER_SIZE);
+        // at least one level, so that empty data is supported
+        let lvls = (std::mem::size_of::<usize>() * 8 - blocks.leading_zeros() as usize).max(1);
 
         let mut res = Self {
             table: vec![Vec::new(); lvls],
             inners: vec![Inner::new(); blocks],
-            data,
         };
 
         res.table[0].reserve(blocks);
         for block in 0..blocks {
             let begin = block * INNER_SIZE;
             let end = (begin + INNER_SIZE).min(n);
-            res.table[0].push(begin as u32 + res.inners[block].init(&data[begin..end]));
+            let argmin = res.inners[block].init(&data[begin..end], order);
+            res.table[0].push(begin as u32 + argmin);
         }
 
         let mut width = 1;
         for lvl in 1..lvls {
             res.table[lvl].reserve(blocks - 2 * width + 1);
             for i in 0..=blocks - 2 * width {
-                let min = res.select(res.table[lvl - 1][i], res.table[lvl - 1][i + width]);
+                let min = select(
+                    data,
+                    order,
+                    res.table[lvl - 1][i],
+                    res.table[lvl - 1][i + width],
+                );
                 res.table[lvl].push(min);
             }
             width *= 2;
@@ -78,22 +117,10 @@ impl<'a, T: Ord + Clone + Debug> RMQ<'a, T> {
         res
     }
 
-    pub fn argmin(&self, range: impl RangeBounds<usize>) -> usize {
-        use std::ops::Bound::*;
-
-        let i = match range.start_bound() {
-            Included(&n) => n,
-            Excluded(&n) => n + 1,
-            Unbounded => 0,
-        };
-        let j = match range.end_bound() {
-            Included(&n) => n + 1,
-            Excluded(&n) => n,
-            Unbounded => self.data.len(),
-        };
-
+    fn argmin<T, O: Order<T>>(&self, data: &[T], order: &O, i: usize, j: usize) -> usize {
         assert!(i < j);
 
+        let select = |i, j| select(data, order, i, j);
         let i_block = i / INNER_SIZE;
         let j_block = (j - 1) / INNER_SIZE;
         let i_offs = i % INNER_SIZE;
@@ -102,7 +129,7 @@ impl<'a, T: Ord + Clone + Debug> RMQ<'a, T> {
         if i_block == j_block {
             self.inner_argmin(i_block, i_offs, j_offs) as usize
         } else if i_block + 1 == j_block {
-            self.select(
+            select(
                 self.inner_argmin(i_block, i_offs, INNER_SIZE),
                 self.inner_argmin(j_block, 0, j_offs),
             ) as usize
@@ -112,30 +139,155 @@ impl<'a, T: Ord + Clone + Debug> RMQ<'a, T> {
                 - 1;
             let w = 1 << lvl;
 
-            self.select(
-                self.select(
+            select(
+                select(
                     self.inner_argmin(i_block, i_offs, INNER_SIZE),
                     self.inner_argmin(j_block, 0, j_offs),
                 ),
-                self.select(self.table[lvl][i_block + 1], self.table[lvl][j_block - w]),
+                select(self.table[lvl][i_block + 1], self.table[lvl][j_block - w]),
             ) as usize
         }
     }
 
+    fn inner_argmin(&self, block: usize, i: usize, j: usize) -> u32 {
+        let res = self.inners[block].argmin(i as u32, j as u32);
+        (block * INNER_SIZE) as u32 + res
+    }
+}
+
+fn select<T, O: Order<T>>(data: &[T], order: &O, i: u32, j: u32) -> u32 {
+    if order.less(&data[i as usize], &data[j as usize]) {
+        i
+    } else {
+        j
+    }
+}
+
+/// Static range minimum queries in O(1) after O(n) preprocessing, with
+/// respect to any [`Order`].
+///
+/// See [`OwnedRMQ`] for a version owning its data.
+///
+/// ```
+/// use hcpl_rmq::{ReverseOrder, RMQ};
+///
+/// let data = [3, 1, 4, 1, 5, 9, 2, 6];
+/// let rmq = RMQ::new(&data);
+/// assert_eq!(rmq.argmin(..3), 1);
+/// assert_eq!(*rmq.min(4..), 2);
+///
+/// let rmq = RMQ::with_order(&data, ReverseOrder);
+/// assert_eq!(rmq.argmin(..), 5);
+///
+/// let pairs = [(1, 'a'), (0, 'b'), (2, 'c')];
+/// let rmq = RMQ::with_order(&pairs, |a: &(i32, char), b: &(i32, char)| a.1 > b.1);
+/// assert_eq!(*rmq.min(..2), (0, 'b'));
+/// ```
+pub struct RMQ<'a, T, O = NaturalOrder> {
+    index: Index,
+    data: &'a [T],
+    order: O,
+}
+
+impl<'a, T: Ord> RMQ<'a, T> {
+    pub fn new(data: &'a [T]) -> Self {
+        Self::with_order(data, NaturalOrder)
+    }
+}
+
+impl<'a, T, O: Order<T>> RMQ<'a, T, O> {
+    pub fn with_order(data: &'a [T], order: O) -> Self {
+        Self {
+            index: Index::new(data, &order),
+            data,
+            order,
+        }
+    }
+
+    pub fn argmin(&self, range: impl RangeBounds<usize>) -> usize {
+        let (i, j) = to_bounds(range, self.data.len());
+        self.index.argmin(self.data, &self.order, i, j)
+    }
+
     pub fn min(&self, range: impl RangeBounds<usize>) -> &T {
         &self.data[self.argmin(range)]
     }
 
-    fn inner_argmin(&self, block: usize, i: usize, j: usize) -> u32 {
-        let res = self.inners[block].argmin(i as u32, j as u32);
-        (block * INNER_SIZE) as u32 + res
+    /// Returns the data the queries are answered on.
+    pub fn data(&self) -> &'a [T] {
+        self.data
     }
+}
 
-    fn select(&self, i: u32, j: u32) -> u32 {
-        if self.data[i as usize] < self.data[j as usize] {
-            i
-        } else {
-            j
+/// Like [`RMQ`], but owning its data, so that it can be stored alongside
+/// other structures.
+///
+/// ```
+/// use hcpl_rmq::OwnedRMQ;
+///
+/// let rmq: OwnedRMQ<_> = [3, 1, 4, 1, 5].into_iter().collect();
+/// assert_eq!(*rmq.min(2..), 1);
+/// assert_eq!(rmq.data(), [3, 1, 4, 1, 5]);
+/// ```
+pub struct OwnedRMQ<T, O = NaturalOrder> {
+    index: Index,
+    data: Vec<T>,
+    order: O,
+}
+
+impl<T: Ord> OwnedRMQ<T> {
+    pub fn new(data: Vec<T>) -> Self {
+        Self::with_order(data, NaturalOrder)
+    }
+}
+
+impl<T, O: Order<T>> OwnedRMQ<T, O> {
+    pub fn with_order(data: Vec<T>, order: O) -> Self {
+        Self {
+            index: Index::new(&data, &order),
+            data,
+            order,
         }
     }
+
+    pub fn argmin(&self, range: impl RangeBounds<usize>) -> usize {
+        let (i, j) = to_bounds(range, self.data.len());
+        self.index.argmin(&self.data, &self.order, i, j)
+    }
+
+    pub fn min(&self, range: impl RangeBounds<usize>) -> &T {
+        &self.data[self.argmin(range)]
+    }
+
+    /// Returns the data the queries are answered on.
+    pub fn data(&self) -> &[T] {
+        &self.data
+    }
+
+    /// Returns the data the queries are answered on, dropping the index.
+    pub fn into_data(self) -> Vec<T> {
+        self.data
+    }
+}
+
+impl<T: Ord> FromIterator<T> for OwnedRMQ<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
+
+fn to_bounds<R: RangeBounds<usize>>(range: R, len: usize) -> (usize, usize) {
+    use std::ops::Bound::*;
+
+    let start = match range.start_bound() {
+        Included(&n) => n,
+        Excluded(&n) => n + 1,
+        Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Included(&n) => n + 1,
+        Excluded(&n) => n,
+        Unbounded => len,
+    };
+    (start, end)
 }
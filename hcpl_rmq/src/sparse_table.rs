@@ -0,0 +1,64 @@
+use super::to_bounds;
+use hcpl_algebra::{Idempotent, Semigroup};
+use std::ops::RangeBounds;
+
+/// Static range queries over an idempotent semigroup, eg. min, max, gcd,
+/// bitwise and / or, in O(1) after O(n log n) preprocessing.
+///
+/// ```
+/// use hcpl_algebra::monoid::GcdMonoid;
+/// use hcpl_rmq::SparseTable;
+///
+/// let table: SparseTable<_> = [12, 18, 8, 20].into_iter().map(GcdMonoid).collect();
+/// assert_eq!(table.fold(0..2), GcdMonoid(6));
+/// assert_eq!(table.fold(..), GcdMonoid(2));
+/// ```
+pub struct SparseTable<T> {
+    /// `table[k][i]` is the product of `data[i..i + 2^k]`.
+    table: Vec<Vec<T>>,
+}
+
+impl<T: Idempotent + Clone> SparseTable<T> {
+    pub fn new(data: Vec<T>) -> Self {
+        let n = data.len();
+        let mut table = vec![data];
+
+        let mut width = 1;
+        while 2 * width <= n {
+            let prev = table.last().unwrap();
+            let next = (0..=n - 2 * width)
+                .map(|i| Semigroup::op(prev[i].clone(), prev[i + width].clone()))
+                .collect();
+            table.push(next);
+            width *= 2;
+        }
+
+        Self { table }
+    }
+
+    pub fn len(&self) -> usize {
+        self.table[0].len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the product of `data[range]`, which must not be empty.
+    pub fn fold<R: RangeBounds<usize>>(&self, range: R) -> T {
+        let (i, j) = to_bounds(range, self.len());
+        assert!(i < j && j <= self.len());
+
+        let lvl = (usize::BITS - (j - i).leading_zeros() - 1) as usize;
+        Semigroup::op(
+            self.table[lvl][i].clone(),
+            self.table[lvl][j - (1 << lvl)].clone(),
+        )
+    }
+}
+
+impl<T: Idempotent + Clone> FromIterator<T> for SparseTable<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        Self::new(iter.into_iter().collect())
+    }
+}
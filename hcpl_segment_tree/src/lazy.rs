@@ -0,0 +1,345 @@
+use super::to_bounds;
+use hcpl_algebra::monoid::{Monoid, MonoidAction};
+use std::{iter::FromIterator, ops::RangeBounds};
+
+#[derive(Clone)]
+/// A segment tree with values of type `T`, supporting range updates by
+/// elements of the monoid `F` acting on `T`.
+///
+/// ```
+/// use hcpl_segment_tree::monoid::{MinMonoid, Monoid, MonoidAction, Semigroup};
+/// use hcpl_segment_tree::LazySegmentTree;
+///
+/// // adding a constant to every element of a range
+/// #[derive(Clone)]
+/// struct Add(i64);
+///
+/// impl Semigroup for Add {
+///     fn op(l: Self, r: Self) -> Self {
+///         Add(l.0 + r.0)
+///     }
+/// }
+///
+/// impl Monoid for Add {
+///     const IDENTITY: Self = Add(0);
+/// }
+///
+/// impl MonoidAction<MinMonoid<i64>> for Add {
+///     fn apply(f: &Self, x: &MinMonoid<i64>) -> MinMonoid<i64> {
+///         MinMonoid(x.0 + f.0)
+///     }
+/// }
+///
+/// let mut tree: LazySegmentTree<_, Add> = [5, 3, 8, 6].map(MinMonoid).into_iter().collect();
+/// tree.apply(0..2, &Add(4)); // [9, 7, 8, 6]
+/// assert_eq!(tree.fold(..).0, 6);
+/// assert_eq!(tree.fold(0..3).0, 7);
+/// assert_eq!(tree.get(1).0, 7);
+/// assert_eq!(tree.right_while(0, |x| x.0 > 6), (3, MinMonoid(7)));
+/// ```
+pub struct LazySegmentTree<T: Monoid + Clone, F: MonoidAction<T> + Clone> {
+    n: usize,
+    log: u32,
+    values: Vec<T>,
+    lazy: Vec<F>,
+}
+
+impl<T: Monoid + Clone, F: MonoidAction<T> + Clone> LazySegmentTree<T, F> {
+    fn new_inner<G: FnOnce(&mut Vec<T>)>(n: usize, g: G) -> Self {
+        let offset = n.next_power_of_two();
+        let mut values = Vec::with_capacity(2 * offset);
+        values.extend(std::iter::repeat_n(T::IDENTITY, offset));
+        g(&mut values);
+        debug_assert!(values.len() <= 2 * offset);
+        values.extend(std::iter::repeat_n(T::IDENTITY, 2 * offset - values.len()));
+        let mut res = Self {
+            n,
+            log: offset.trailing_zeros(),
+            values,
+            lazy: vec![F::IDENTITY; offset],
+        };
+        for i in (1..offset).rev() {
+            res.pull(i);
+        }
+        res
+    }
+
+    /// Creates a lazy segment tree from a slice.
+    pub fn new(src: &[T]) -> Self {
+        Self::new_inner(src.len(), |v| v.extend_from_slice(src))
+    }
+
+    /// Creates a lazy segment tree initialised with `T::IDENTITY`.
+    pub fn with_size(size: usize) -> Self {
+        let offset = size.next_power_of_two();
+        LazySegmentTree {
+            n: size,
+            log: offset.trailing_zeros(),
+            values: vec![T::IDENTITY; offset * 2],
+            lazy: vec![F::IDENTITY; offset],
+        }
+    }
+}
+
+impl<T: Monoid + Clone, F: MonoidAction<T> + Clone> FromIterator<T> for LazySegmentTree<T, F> {
+    fn from_iter<It: IntoIterator<Item = T>>(iter: It) -> Self {
+        let iter = iter.into_iter();
+        let (lower, upper) = iter.size_hint();
+        if Some(lower) == upper {
+            Self::new_inner(lower, |v| v.extend(iter))
+        } else {
+            let all_items: Vec<_> = iter.collect();
+            Self::new(&all_items)
+        }
+    }
+}
+
+impl<T: Monoid + Clone, F: MonoidAction<T> + Clone> LazySegmentTree<T, F> {
+    fn offset(&self) -> usize {
+        self.lazy.len()
+    }
+
+    fn pull(&mut self, i: usize) {
+        debug_assert!(i < self.offset());
+        self.values[i] = T::op(self.values[2 * i].clone(), self.values[2 * i + 1].clone());
+    }
+
+    /// Applies `f` to the whole subtree rooted at node `i`.
+    fn apply_at(&mut self, i: usize, f: &F) {
+        self.values[i] = F::apply(f, &self.values[i]);
+        if i < self.offset() {
+            self.lazy[i] = F::op(f.clone(), self.lazy[i].clone());
+        }
+    }
+
+    /// Propagates the pending action at node `i` to its children.
+    fn push(&mut self, i: usize) {
+        let f = std::mem::replace(&mut self.lazy[i], F::IDENTITY);
+        self.apply_at(2 * i, &f);
+        self.apply_at(2 * i + 1, &f);
+    }
+
+    /// Pushes all pending actions on the path from the root to leaf `i`.
+    fn push_to_leaf(&mut self, i: usize) {
+        for k in (1..=self.log).rev() {
+            self.push(i >> k);
+        }
+    }
+
+    /// Returns an immutable reference to the leaf node with the given `index`.
+    pub fn get(&mut self, index: usize) -> &T {
+        debug_assert!(index < self.n);
+
+        let i = index + self.offset();
+        self.push_to_leaf(i);
+        &self.values[i]
+    }
+
+    /// Replaces `tree[index]` with `value`.
+    pub fn set(&mut self, index: usize, value: T) {
+        debug_assert!(index < self.n);
+
+        let i = index + self.offset();
+        self.push_to_leaf(i);
+        self.values[i] = value;
+        for k in 1..=self.log {
+            self.pull(i >> k);
+        }
+    }
+
+    /// Returns the monoid fold of all the values in the given `range`.
+    pub fn fold<R>(&mut self, range: R) -> T
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = to_bounds(range, self.n);
+
+        if start >= end {
+            return T::IDENTITY;
+        }
+
+        debug_assert!(end <= self.n);
+        let offset = self.offset();
+
+        let mut i = start + offset;
+        let mut j = end + offset;
+
+        for k in (1..=self.log).rev() {
+            if (i >> k) << k != i {
+                self.push(i >> k);
+            }
+            if (j >> k) << k != j {
+                self.push((j - 1) >> k);
+            }
+        }
+
+        let mut l = T::IDENTITY;
+        let mut r = T::IDENTITY;
+
+        while i < j {
+            if i & 1 == 1 {
+                l = T::op(l, self.values[i].clone());
+                i += 1;
+            }
+            if j & 1 == 1 {
+                j -= 1;
+                r = T::op(self.values[j].clone(), r);
+            }
+
+            i >>= 1;
+            j >>= 1;
+        }
+
+        T::op(l, r)
+    }
+
+    /// Replaces every `tree[i]` for `i` in `range` with `F::apply(f, tree[i])`.
+    pub fn apply<R>(&mut self, range: R, f: &F)
+    where
+        R: RangeBounds<usize>,
+    {
+        let (start, end) = to_bounds(range, self.n);
+
+        if start >= end {
+            return;
+        }
+
+        debug_assert!(end <= self.n);
+        let offset = self.offset();
+
+        let start = start + offset;
+        let end = end + offset;
+
+        for k in (1..=self.log).rev() {
+            if (start >> k) << k != start {
+                self.push(start >> k);
+            }
+            if (end >> k) << k != end {
+                self.push((end - 1) >> k);
+            }
+        }
+
+        let mut i = start;
+        let mut j = end;
+        while i < j {
+            if i & 1 == 1 {
+                self.apply_at(i, f);
+                i += 1;
+            }
+            if j & 1 == 1 {
+                j -= 1;
+                self.apply_at(j, f);
+            }
+
+            i >>= 1;
+            j >>= 1;
+        }
+
+        for k in 1..=self.log {
+            if (start >> k) << k != start {
+                self.pull(start >> k);
+            }
+            if (end >> k) << k != end {
+                self.pull((end - 1) >> k);
+            }
+        }
+    }
+
+    /// Returns the largest `end` such that `predicate` holds for the fold of
+    /// `start..end`, along with that fold, assuming `predicate` is monotone.
+    ///
+    /// `predicate(&T::IDENTITY)` must hold.
+    pub fn right_while<P: Fn(&T) -> bool>(&mut self, start: usize, predicate: P) -> (usize, T) {
+        debug_assert!(start <= self.n);
+        debug_assert!(predicate(&T::IDENTITY));
+
+        if start == self.n {
+            return (start, T::IDENTITY);
+        }
+
+        let offset = self.offset();
+        let mut i = start + offset;
+        self.push_to_leaf(i);
+        let mut sum = T::IDENTITY;
+        let mut nxt_sum;
+
+        loop {
+            i >>= i.trailing_zeros();
+
+            nxt_sum = T::op(sum.clone(), self.values[i].clone());
+
+            if !predicate(&nxt_sum) {
+                while i < offset {
+                    self.push(i);
+                    i <<= 1;
+                    nxt_sum = T::op(sum.clone(), self.values[i].clone());
+                    if predicate(&nxt_sum) {
+                        sum = nxt_sum;
+                        i += 1;
+                    }
+                }
+                // it's ok to go beyond n and take min after
+                // because [n,...) are only T::IDENTITY
+                return (std::cmp::min(i - offset, self.n), sum);
+            }
+
+            sum = nxt_sum;
+            i += 1;
+
+            if (i & (i - 1)) == 0 {
+                break;
+            }
+        }
+
+        (self.n, sum)
+    }
+
+    /// Returns the smallest `start` such that `predicate` holds for the fold of
+    /// `start..end`, along with that fold, assuming `predicate` is monotone.
+    ///
+    /// `predicate(&T::IDENTITY)` must hold.
+    pub fn left_while<P: Fn(&T) -> bool>(&mut self, end: usize, predicate: P) -> (usize, T) {
+        debug_assert!(end <= self.n);
+        debug_assert!(predicate(&T::IDENTITY));
+
+        if end == 0 {
+            return (0, T::IDENTITY);
+        }
+
+        let offset = self.offset();
+        let mut i = end + offset;
+        self.push_to_leaf(i - 1);
+        let mut sum = T::IDENTITY;
+        let mut nxt_sum;
+
+        loop {
+            i -= 1;
+            while i > 1 && i & 1 == 1 {
+                i >>= 1;
+            }
+
+            nxt_sum = T::op(self.values[i].clone(), sum.clone());
+
+            if !predicate(&nxt_sum) {
+                while i < offset {
+                    self.push(i);
+                    i = 2 * i + 1;
+                    nxt_sum = T::op(self.values[i].clone(), sum.clone());
+                    if predicate(&nxt_sum) {
+                        sum = nxt_sum;
+                        i -= 1;
+                    }
+                }
+                return (i + 1 - offset, sum);
+            }
+
+            sum = nxt_sum;
+
+            if (i & (i - 1)) == 0 {
+                break;
+            }
+        }
+
+        (0, sum)
+    }
+}
@@ -1,7 +1,10 @@
-use hcpl_algebra::monoid::Monoid;
+use hcpl_algebra::monoid::{CommutativeMonoid, Monoid};
 use std::{iter::FromIterator, ops::RangeBounds};
 
+mod lazy;
+
 pub use hcpl_algebra::monoid;
+pub use lazy::LazySegmentTree;
 
 #[derive(Clone)]
 /// A segment tree with values of type `T`
@@ -14,10 +17,10 @@ impl<T: Monoid + Clone> SegmentTree<T> {
     fn new_inner<F: FnOnce(&mut Vec<T>)>(n: usize, f: F) -> Self {
         let offset = n.next_power_of_two();
         let mut values = Vec::with_capacity(2 * offset);
-        values.extend(std::iter::repeat(T::IDENTITY).take(offset));
+        values.extend(std::iter::repeat_n(T::IDENTITY, offset));
         f(&mut values);
         debug_assert!(values.len() <= 2 * offset);
-        values.extend(std::iter::repeat(T::IDENTITY).take(2 * offset - values.len()));
+        values.extend(std::iter::repeat_n(T::IDENTITY, 2 * offset - values.len()));
         let mut res = Self { n, values };
         for i in (1..offset).rev() {
             res.pull(i);
@@ -91,6 +94,24 @@ fn parent_chain(idx: usize) -> impl Iterator<Item = usize> {
     std::iter::successors(try_parent(idx), |&idx| try_parent(idx))
 }
 
+/// Converts `range` into a half-open interval `(start, end)`,
+/// where an unbounded end is replaced with `len`.
+fn to_bounds<R: RangeBounds<usize>>(range: R, len: usize) -> (usize, usize) {
+    use std::ops::Bound::*;
+
+    let start = match range.start_bound() {
+        Included(&n) => n,
+        Excluded(&n) => n + 1,
+        Unbounded => 0,
+    };
+    let end = match range.end_bound() {
+        Included(&n) => n + 1,
+        Excluded(&n) => n,
+        Unbounded => len,
+    };
+    (start, end)
+}
+
 impl<T: Monoid + Clone> SegmentTree<T> {
     fn pull(&mut self, i: usize) {
         debug_assert!(i < self.offset());
@@ -118,33 +139,12 @@ impl<T: Monoid + Clone> SegmentTree<T> {
         &self.values[index + self.offset()]
     }
 
-    /// Replaces `tree[index]` with `T::op(tree[index], value)`.
-    pub fn add(&mut self, mut index: usize, value: &T) {
-        index += self.offset();
-        self.values[index] = T::op(self.values[index].clone(), (*value).clone());
-
-        for i in parent_chain(index) {
-            self.values[i] = T::op(self.values[i].clone(), value.clone());
-        }
-    }
-
     /// Returns the monoid fold of all the values in the given `range`.
     pub fn fold<R>(&self, range: R) -> T
     where
         R: RangeBounds<usize>,
     {
-        use std::ops::Bound::*;
-
-        let start = match range.start_bound() {
-            Included(&n) => n,
-            Excluded(&n) => n + 1,
-            Unbounded => 0,
-        };
-        let end = match range.end_bound() {
-            Included(&n) => n + 1,
-            Excluded(&n) => n,
-            Unbounded => self.n,
-        };
+        let (start, end) = to_bounds(range, self.n);
 
         if start > end {
             return T::IDENTITY;
@@ -174,6 +174,10 @@ impl<T: Monoid + Clone> SegmentTree<T> {
         T::op(l, r)
     }
 
+    /// Returns the largest `end` such that `predicate` holds for the fold of
+    /// `start..end`, along with that fold, assuming `predicate` is monotone.
+    ///
+    /// `predicate(&T::IDENTITY)` must hold.
     pub fn right_while<P: Fn(&T) -> bool>(&self, start: usize, predicate: P) -> (usize, T) {
         debug_assert!(start <= self.n);
         debug_assert!(predicate(&T::IDENTITY));
@@ -216,6 +220,65 @@ impl<T: Monoid + Clone> SegmentTree<T> {
 
         (self.n, sum)
     }
+
+    /// Returns the smallest `start` such that `predicate` holds for the fold of
+    /// `start..end`, along with that fold, assuming `predicate` is monotone.
+    ///
+    /// `predicate(&T::IDENTITY)` must hold.
+    pub fn left_while<P: Fn(&T) -> bool>(&self, end: usize, predicate: P) -> (usize, T) {
+        debug_assert!(end <= self.n);
+        debug_assert!(predicate(&T::IDENTITY));
+
+        if end == 0 {
+            return (0, T::IDENTITY);
+        }
+
+        let offset = self.offset();
+        let mut i = end + offset;
+        let mut sum = T::IDENTITY;
+        let mut nxt_sum;
+
+        loop {
+            i -= 1;
+            while i > 1 && i & 1 == 1 {
+                i >>= 1;
+            }
+
+            nxt_sum = T::op(self.values[i].clone(), sum.clone());
+
+            if !predicate(&nxt_sum) {
+                while i < offset {
+                    i = 2 * i + 1;
+                    nxt_sum = T::op(self.values[i].clone(), sum.clone());
+                    if predicate(&nxt_sum) {
+                        sum = nxt_sum;
+                        i -= 1;
+                    }
+                }
+                return (i + 1 - offset, sum);
+            }
+
+            sum = nxt_sum;
+
+            if (i & (i - 1)) == 0 {
+                break;
+            }
+        }
+
+        (0, sum)
+    }
+}
+
+impl<T: CommutativeMonoid + Clone> SegmentTree<T> {
+    /// Replaces `tree[index]` with `T::op(tree[index], value)`.
+    pub fn add(&mut self, mut index: usize, value: &T) {
+        index += self.offset();
+        self.values[index] = T::op(self.values[index].clone(), (*value).clone());
+
+        for i in parent_chain(index) {
+            self.values[i] = T::op(self.values[i].clone(), value.clone());
+        }
+    }
 }
 
 impl<T: Monoid + Clone + std::fmt::Debug> std::fmt::Debug for SegmentTree<T> {
@@ -0,0 +1,2 @@
+mod suffix_array;
+pub use suffix_array::{lcp_array, suffix_array, SuffixArray};
@@ -0,0 +1,271 @@
+use hcpl_rmq::OwnedRMQ;
+use std::cmp::Ordering;
+use std::ops::Range;
+
+/// SA-IS on a string of integers in `0..=upper`, in O(n + upper).
+fn sa_is(s: &[usize], upper: usize) -> Vec<usize> {
+    const NONE: usize = usize::MAX;
+
+    let n = s.len();
+    match n {
+        0 => return Vec::new(),
+        1 => return vec![0],
+        2 => return if s[0] < s[1] { vec![0, 1] } else { vec![1, 0] },
+        _ => {}
+    }
+
+    // ls[i] is true if the suffix at i is S-type, ie. smaller than the one at i + 1
+    let mut ls = vec![false; n];
+    for i in (0..n - 1).rev() {
+        ls[i] = if s[i] == s[i + 1] {
+            ls[i + 1]
+        } else {
+            s[i] < s[i + 1]
+        };
+    }
+
+    // bucket boundaries: sum_l[c] is the start of the bucket of c,
+    // sum_s[c] the start of its S-type part
+    let mut sum_l = vec![0; upper + 1];
+    let mut sum_s = vec![0; upper + 1];
+    for i in 0..n {
+        if !ls[i] {
+            sum_s[s[i]] += 1;
+        } else {
+            sum_l[s[i] + 1] += 1;
+        }
+    }
+    for c in 0..=upper {
+        sum_s[c] += sum_l[c];
+        if c < upper {
+            sum_l[c + 1] += sum_s[c];
+        }
+    }
+
+    let induce = |lms: &[usize], sa: &mut [usize]| {
+        sa.fill(NONE);
+        let mut buf = sum_s.clone();
+        for &d in lms {
+            if d != n {
+                sa[buf[s[d]]] = d;
+                buf[s[d]] += 1;
+            }
+        }
+
+        buf.copy_from_slice(&sum_l);
+        sa[buf[s[n - 1]]] = n - 1;
+        buf[s[n - 1]] += 1;
+        for i in 0..n {
+            let v = sa[i];
+            if v != NONE && v >= 1 && !ls[v - 1] {
+                sa[buf[s[v - 1]]] = v - 1;
+                buf[s[v - 1]] += 1;
+            }
+        }
+
+        buf.copy_from_slice(&sum_l);
+        for i in (0..n).rev() {
+            let v = sa[i];
+            if v != NONE && v >= 1 && ls[v - 1] {
+                buf[s[v - 1] + 1] -= 1;
+                sa[buf[s[v - 1] + 1]] = v - 1;
+            }
+        }
+    };
+
+    let mut lms_map = vec![NONE; n + 1];
+    let mut lms = Vec::new();
+    for i in 1..n {
+        if !ls[i - 1] && ls[i] {
+            lms_map[i] = lms.len();
+            lms.push(i);
+        }
+    }
+    let m = lms.len();
+
+    let mut sa = vec![NONE; n];
+    induce(&lms, &mut sa);
+
+    if m != 0 {
+        // name the LMS substrings and sort them recursively
+        let mut sorted_lms: Vec<usize> =
+            sa.iter().copied().filter(|&v| lms_map[v] != NONE).collect();
+        let mut rec_s = vec![0; m];
+        let mut rec_upper = 0;
+        for i in 1..m {
+            let mut l = sorted_lms[i - 1];
+            let mut r = sorted_lms[i];
+            let end_l = lms.get(lms_map[l] + 1).copied().unwrap_or(n);
+            let end_r = lms.get(lms_map[r] + 1).copied().unwrap_or(n);
+
+            let mut same = end_l - l == end_r - r;
+            if same {
+                while l < end_l && s[l] == s[r] {
+                    l += 1;
+                    r += 1;
+                }
+                if l == n || s[l] != s[r] {
+                    same = false;
+                }
+            }
+
+            if !same {
+                rec_upper += 1;
+            }
+            rec_s[lms_map[sorted_lms[i]]] = rec_upper;
+        }
+
+        let rec_sa = sa_is(&rec_s, rec_upper);
+        for (x, &i) in sorted_lms.iter_mut().zip(&rec_sa) {
+            *x = lms[i];
+        }
+        induce(&sorted_lms, &mut sa);
+    }
+
+    sa
+}
+
+/// Returns the starting positions of the suffixes of `s` in lexicographic order,
+/// in O(n log n) for compressing the alphabet and O(n) for SA-IS itself.
+///
+/// ```
+/// use hcpl_string::suffix_array;
+///
+/// assert_eq!(suffix_array(b"banana"), [5, 3, 1, 0, 4, 2]);
+/// ```
+pub fn suffix_array<T: Ord>(s: &[T]) -> Vec<usize> {
+    let n = s.len();
+    let mut order: Vec<usize> = (0..n).collect();
+    order.sort_by(|&i, &j| s[i].cmp(&s[j]));
+
+    let mut compressed = vec![0; n];
+    let mut upper = 0;
+    for k in 0..n {
+        if k > 0 && s[order[k - 1]] != s[order[k]] {
+            upper += 1;
+        }
+        compressed[order[k]] = upper;
+    }
+
+    sa_is(&compressed, upper)
+}
+
+/// Returns the longest common prefix of each pair of adjacent suffixes in the
+/// suffix array `sa` of `s`, using Kasai's algorithm in O(n).
+///
+/// The result has length `n - 1`, and its `i`th element is the LCP of the
+/// suffixes starting at `sa[i]` and `sa[i + 1]`.
+pub fn lcp_array<T: Eq>(s: &[T], sa: &[usize]) -> Vec<usize> {
+    let n = s.len();
+    if n == 0 {
+        return Vec::new();
+    }
+
+    let mut rank = vec![0; n];
+    for (i, &p) in sa.iter().enumerate() {
+        rank[p] = i;
+    }
+
+    let mut lcp = vec![0; n - 1];
+    let mut h: usize = 0;
+    for i in 0..n {
+        h = h.saturating_sub(1);
+        if rank[i] == 0 {
+            continue;
+        }
+        let j = sa[rank[i] - 1];
+        while i + h < n && j + h < n && s[i + h] == s[j + h] {
+            h += 1;
+        }
+        lcp[rank[i] - 1] = h;
+    }
+    lcp
+}
+
+/// A suffix array with its LCP array, answering longest common prefix
+/// queries on any two suffixes and substring comparisons in O(1).
+///
+/// ```
+/// use hcpl_string::SuffixArray;
+/// use std::cmp::Ordering;
+///
+/// let s = b"abracadabra";
+/// let sa = SuffixArray::new(s);
+/// assert_eq!(sa.lcp(0, 7), 4); // "abra"
+/// assert_eq!(sa.lcp(1, 8), 3); // "bra"
+/// assert_eq!(sa.compare(0..4, 7..11), Ordering::Equal);
+/// assert_eq!(sa.compare(0..2, 3..5), Ordering::Less); // "ab" < "ac"
+/// assert_eq!(sa.compare(7..11, 7..10), Ordering::Greater);
+/// ```
+pub struct SuffixArray {
+    sa: Vec<usize>,
+    rank: Vec<usize>,
+    lcp: OwnedRMQ<usize>,
+}
+
+impl SuffixArray {
+    /// Builds the suffix array, the LCP array and its range minimum query structure.
+    pub fn new<T: Ord>(s: &[T]) -> Self {
+        let sa = suffix_array(s);
+        let mut rank = vec![0; s.len()];
+        for (i, &p) in sa.iter().enumerate() {
+            rank[p] = i;
+        }
+        let lcp = OwnedRMQ::new(lcp_array(s, &sa));
+        Self { sa, rank, lcp }
+    }
+
+    /// Returns the length of the string.
+    pub fn len(&self) -> usize {
+        self.sa.len()
+    }
+
+    /// Returns `true` if the string is empty.
+    pub fn is_empty(&self) -> bool {
+        self.sa.is_empty()
+    }
+
+    /// Returns the starting positions of the suffixes in lexicographic order.
+    pub fn sa(&self) -> &[usize] {
+        &self.sa
+    }
+
+    /// Returns the position of each suffix in [`SuffixArray::sa`].
+    pub fn rank(&self) -> &[usize] {
+        &self.rank
+    }
+
+    /// Returns the LCP of each pair of adjacent suffixes in [`SuffixArray::sa`].
+    pub fn lcp_array(&self) -> &[usize] {
+        self.lcp.data()
+    }
+
+    /// Returns the length of the longest common prefix of the suffixes
+    /// starting at `i` and `j`, which may be equal to the length of the string.
+    pub fn lcp(&self, i: usize, j: usize) -> usize {
+        let n = self.len();
+        if i == n || j == n {
+            return 0;
+        }
+        if i == j {
+            return n - i;
+        }
+
+        let (a, b) = if self.rank[i] < self.rank[j] {
+            (self.rank[i], self.rank[j])
+        } else {
+            (self.rank[j], self.rank[i])
+        };
+        *self.lcp.min(a..b)
+    }
+
+    /// Compares the substrings `s[a]` and `s[b]` lexicographically.
+    pub fn compare(&self, a: Range<usize>, b: Range<usize>) -> Ordering {
+        let common = self.lcp(a.start, b.start);
+        if common >= a.len().min(b.len()) {
+            a.len().cmp(&b.len())
+        } else {
+            self.rank[a.start].cmp(&self.rank[b.start])
+        }
+    }
+}